@@ -39,7 +39,7 @@ async fn main() {
 	} else {
 		let vars = format!(
 			"RUES_DEVICE_TYPE='{}'\nRUES_APPLICATION_KEY='{}'\nRUES_BRIDGE='{}'\n",
-			device_type.to_string(),
+			device_type,
 			hue.application_key().expect("Application key expected."),
 			arguments.address
 		);
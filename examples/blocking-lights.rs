@@ -0,0 +1,38 @@
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use huehue::blocking::Hue;
+use huehue::models::device_type::DeviceType;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Serialize)]
+struct Arguments {
+	#[structopt(long, env = "RUES_BRIDGE")]
+	pub bridge: Ipv4Addr,
+
+	#[structopt(long, env = "RUES_DEVICE_TYPE")]
+	pub device_type: String,
+
+	#[structopt(long, env = "RUES_APPLICATION_KEY")]
+	pub application_key: String,
+}
+
+fn main() {
+	let arguments = Arguments::from_args();
+
+	let device_type = DeviceType::from_str(arguments.device_type.as_str()).expect("Invalid device name.");
+	let hue = Hue::new_with_key(arguments.bridge, device_type, arguments.application_key)
+		.expect("Failed to read bridge information.");
+
+	match hue.lights() {
+		Ok(lights) => {
+			lights.iter().for_each(|light| {
+				println!("> Light {}:", light.name);
+				println!("\tIdentifier: {}", light.id);
+				println!("\tOn: {}", light.on);
+			})
+		},
+		Err(e) => println!("Unexpected Hue error {:?}.", e),
+	}
+}
@@ -45,7 +45,7 @@ async fn main() {
 		.expect("Failed to read bridge information.");
 
 	match hue.devices().await {
-		Ok(devices) => devices.iter().for_each(|device| print_device(device)),
+		Ok(devices) => devices.iter().for_each(print_device),
 		Err(e) => println!("Unexpected Hue error {:?}.", e),
 	}
 }
@@ -0,0 +1,59 @@
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use huehue::models::device_type::DeviceType;
+use huehue::Hue;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Serialize)]
+enum ActionArguments {
+	On,
+	Off,
+}
+
+#[derive(Debug, StructOpt, Serialize)]
+struct Arguments {
+	#[structopt(subcommand)]
+	pub action: ActionArguments,
+
+	#[structopt(long, env = "RUES_BRIDGE")]
+	pub bridge: Ipv4Addr,
+
+	#[structopt(long, env = "RUES_DEVICE_TYPE")]
+	pub device_type: String,
+
+	#[structopt(long, env = "RUES_APPLICATION_KEY")]
+	pub application_key: String,
+}
+
+#[tokio::main]
+async fn main() {
+	let arguments = Arguments::from_args();
+
+	let device_type = DeviceType::from_str(arguments.device_type.as_str()).expect("Invalid device name.");
+	let hue = Hue::new_with_key(arguments.bridge, device_type, arguments.application_key)
+		.await
+		.expect("Failed to read bridge information.");
+
+	let home = match hue.home().await {
+		Ok(home) => home,
+		Err(e) => {
+			println!("Unexpected Hue error {:?}.", e);
+			return;
+		},
+	};
+
+	println!("> Home {}:", home.id);
+	println!("\tBridge: {}", home.bridge_id);
+	println!("\tTime zone: {}", home.time_zone);
+	home.rooms().for_each(|room| println!("\tRoom: {}", room));
+
+	let on = match arguments.action {
+		ActionArguments::On => true,
+		ActionArguments::Off => false,
+	};
+	if let Err(e) = home.switch(on).await {
+		println!("Unexpected Hue error {:?}.", e);
+	}
+}
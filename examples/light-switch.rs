@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use huehue::color::{Component, RGB8};
 use huehue::models::device_type::DeviceType;
-use huehue::{Hue, Light};
+use huehue::{Hue, HueError, Light};
 use serde::Serialize;
 use structopt::StructOpt;
 
@@ -40,6 +40,7 @@ enum ActionArguments {
 	Dimm(DimmArguments),
 	Xy(XyArguments),
 	Rgb(RgbArguments),
+	Alert,
 }
 
 #[derive(Debug, StructOpt, Serialize)]
@@ -102,18 +103,14 @@ async fn main() {
 		.await
 		.expect("Failed to read bridge information.");
 
-	let light_opt = match hue.lights().await {
-		Ok(lights) => lights.into_iter().filter(|light| light.id == arguments.id).next(),
-		Err(e) => {
-			println!("Unexpected Hue error {:?}.", e);
+	let mut light = match hue.light(arguments.id).await {
+		Ok(light) => light,
+		Err(HueError::NotFound) => {
+			println!("Light {} not found.", arguments.id);
 			return;
 		},
-	};
-
-	let mut light = match light_opt {
-		Some(light) => light,
-		None => {
-			println!("Light {} not found.", arguments.id);
+		Err(e) => {
+			println!("Unexpected Hue error {:?}.", e);
 			return;
 		},
 	};
@@ -149,6 +146,15 @@ async fn main() {
 				},
 			}
 		},
+		ActionArguments::Alert => {
+			match light.alert().await {
+				Ok(_) => (),
+				Err(e) => {
+					println!("Unexpected Hue error {:?}.", e);
+					return;
+				},
+			}
+		},
 		ActionArguments::Dimm(dimm) => {
 			match light.dimm(dimm.value).await {
 				Ok(_) => (),
@@ -61,7 +61,7 @@ async fn main() {
 		.expect("Failed to read bridge information.");
 
 	match hue.lights().await {
-		Ok(lights) => lights.iter().for_each(|light| print_lights(light)),
+		Ok(lights) => lights.iter().for_each(print_lights),
 		Err(e) => println!("Unexpected Hue error {:?}.", e),
 	}
 }
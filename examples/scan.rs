@@ -1,22 +1,50 @@
+use std::str::FromStr;
 use std::time::Duration;
 
+use huehue::discover::{DiscoverySource, Subnet, SubnetScan};
 use huehue::Hue;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Serialize)]
+struct Arguments {
+	/// Also scan every address of this subnet (like 192.168.1.0/24), for networks without mDNS nor internet access.
+	#[structopt(long)]
+	pub subnet: Option<String>,
+}
 
 #[tokio::main]
 async fn main() {
+	let arguments = Arguments::from_args();
+
 	println!("Scanning for bridges for 5 seconds.");
-	let bridges = Hue::bridges(Duration::from_secs(5)).await;
+	let bridges = match arguments.subnet {
+		Some(subnet) => {
+			let subnet = Subnet::from_str(subnet.as_str()).expect("Invalid subnet.");
+			let sources = [
+				DiscoverySource::Mdns,
+				DiscoverySource::Meethue,
+				DiscoverySource::Subnet(SubnetScan::new(subnet)),
+			];
+			Hue::discover_stream(&sources, Duration::from_secs(5)).collect().await
+		},
+		None => Hue::bridges(Duration::from_secs(5)).await,
+	};
 	println!("{} bridge(s) found.\n", bridges.len());
 
-	let mut i = 1;
-	for bridge in &bridges {
-		println!("> Bridge #{}:", i);
-		i += 1;
+	for (i, bridge) in bridges.iter().enumerate() {
+		println!("> Bridge #{}:", i + 1);
 
 		println!("\tIdentifier: {}", bridge.id);
+		println!("\tName: {}", bridge.name);
 		println!("\tModel: {:?}", bridge.model);
 		println!("\tVersion: {}", bridge.version);
+		println!("\tAPI version: {}", bridge.api_version);
 		println!("\tAddress: {}", bridge.address);
+		println!("\tMAC: {}", bridge.mac);
 		println!("\tSupported: {}", bridge.supported);
+		if let Some(source) = &bridge.source {
+			println!("\tSource: {:?}", source);
+		}
 	}
 }
@@ -0,0 +1,37 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::HueError;
+
+pub type Devices = Vec<Device>;
+
+/// Blocking counterpart of [`crate::device::Device`]. Its state is accessible through [`Deref`].
+#[derive(Debug, Clone)]
+pub struct Device {
+	inner: crate::device::Device,
+	runtime: Arc<Runtime>,
+}
+
+impl Device {
+	pub(crate) fn new(inner: crate::device::Device, runtime: Arc<Runtime>) -> Device {
+		Device { inner, runtime }
+	}
+
+	pub fn identify(&self) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.identify())
+	}
+
+	pub fn delete(self) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.delete())
+	}
+}
+
+impl Deref for Device {
+	type Target = crate::device::Device;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
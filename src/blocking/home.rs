@@ -0,0 +1,31 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::HueError;
+
+/// Blocking counterpart of [`crate::Home`]. Its state is accessible through [`Deref`].
+#[derive(Debug, Clone)]
+pub struct Home {
+	inner: crate::Home,
+	runtime: Arc<Runtime>,
+}
+
+impl Home {
+	pub(crate) fn new(inner: crate::Home, runtime: Arc<Runtime>) -> Home {
+		Home { inner, runtime }
+	}
+
+	pub fn switch(&self, on: bool) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.switch(on))
+	}
+}
+
+impl Deref for Home {
+	type Target = crate::Home;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
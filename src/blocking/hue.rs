@@ -0,0 +1,125 @@
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::runtime::Runtime;
+
+use crate::blocking::{Device, Devices, Home, Light, Lights};
+use crate::models::device_type::DeviceType;
+use crate::models::generic::{GenericIdentifier, Resource};
+use crate::transport::Transport;
+use crate::{Bridge, HueError};
+
+/// Blocking counterpart of [`crate::Hue`].
+#[derive(Debug, Clone)]
+pub struct Hue {
+	inner: crate::Hue,
+	runtime: Arc<Runtime>,
+}
+
+impl Hue {
+	pub fn new(ip: Ipv4Addr, device_type: DeviceType) -> Result<Hue, HueError> {
+		let runtime = super::runtime()?;
+		let inner = runtime.block_on(crate::Hue::new(ip, device_type))?;
+
+		Ok(Hue { inner, runtime })
+	}
+
+	pub fn new_with_key(ip: Ipv4Addr, device_type: DeviceType, application_key: String) -> Result<Hue, HueError> {
+		let runtime = super::runtime()?;
+		let inner = runtime.block_on(crate::Hue::new_with_key(ip, device_type, application_key))?;
+
+		Ok(Hue { inner, runtime })
+	}
+
+	pub fn new_with_transport(
+		ip: Ipv4Addr,
+		device_type: DeviceType,
+		application_key: Option<String>,
+		transport: Arc<dyn Transport>,
+	) -> Result<Hue, HueError> {
+		let runtime = super::runtime()?;
+		let inner = runtime.block_on(crate::Hue::new_with_transport(
+			ip,
+			device_type,
+			application_key,
+			transport,
+		))?;
+
+		Ok(Hue { inner, runtime })
+	}
+
+	pub fn bridge(&self) -> &Bridge {
+		self.inner.bridge()
+	}
+
+	pub fn url(&self, path: &str) -> url::Url {
+		self.inner.url(path)
+	}
+
+	pub fn device_type(&self) -> &DeviceType {
+		self.inner.device_type()
+	}
+
+	pub fn application_key(&self) -> Option<String> {
+		self.inner.application_key()
+	}
+
+	pub fn bridges(timeout: Duration) -> Vec<Bridge> {
+		match super::runtime() {
+			Ok(runtime) => runtime.block_on(crate::Hue::bridges(timeout)),
+			Err(_) => Vec::new(),
+		}
+	}
+
+	pub fn authorize(&mut self) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.authorize())
+	}
+
+	pub fn get<T>(&self, id: uuid::Uuid) -> Result<T, HueError>
+	where
+		T: Resource,
+	{
+		self.runtime.block_on(self.inner.get::<T>(id))
+	}
+
+	pub fn create<T, P>(&self, payload: &P) -> Result<GenericIdentifier, HueError>
+	where
+		T: Resource,
+		P: Serialize,
+	{
+		self.runtime.block_on(self.inner.create::<T, P>(payload))
+	}
+
+	pub fn home(&self) -> Result<Home, HueError> {
+		let home = self.runtime.block_on(self.inner.home())?;
+		Ok(Home::new(home, self.runtime.clone()))
+	}
+
+	pub fn lights(&self) -> Result<Lights, HueError> {
+		let lights = self.runtime.block_on(self.inner.lights())?;
+		Ok(lights
+			.into_iter()
+			.map(|light| Light::new(light, self.runtime.clone()))
+			.collect())
+	}
+
+	pub fn light(&self, id: uuid::Uuid) -> Result<Light, HueError> {
+		let light = self.runtime.block_on(self.inner.light(id))?;
+		Ok(Light::new(light, self.runtime.clone()))
+	}
+
+	pub fn devices(&self) -> Result<Devices, HueError> {
+		let devices = self.runtime.block_on(self.inner.devices())?;
+		Ok(devices
+			.into_iter()
+			.map(|device| Device::new(device, self.runtime.clone()))
+			.collect())
+	}
+
+	pub fn device(&self, id: uuid::Uuid) -> Result<Device, HueError> {
+		let device = self.runtime.block_on(self.inner.device(id))?;
+		Ok(Device::new(device, self.runtime.clone()))
+	}
+}
@@ -0,0 +1,110 @@
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::runtime::Runtime;
+
+use crate::color::{Component, GradientMode, Palette, HSV, RGB8};
+use crate::effect::{Effect, Signal, TimedEffect};
+use crate::models::generic::Archetype;
+use crate::powerup::Powerup;
+use crate::HueError;
+
+pub type Lights = Vec<Light>;
+
+/// Blocking counterpart of [`crate::Light`]. Its state is accessible through [`Deref`].
+#[derive(Debug, Clone)]
+pub struct Light {
+	inner: crate::Light,
+	runtime: Arc<Runtime>,
+}
+
+impl Light {
+	pub(crate) fn new(inner: crate::Light, runtime: Arc<Runtime>) -> Light {
+		Light { inner, runtime }
+	}
+
+	pub fn refresh(&mut self) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.refresh())
+	}
+
+	pub fn set_name(&mut self, name: String) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_name(name))
+	}
+
+	pub fn set_archetype(&mut self, archetype: Archetype) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_archetype(archetype))
+	}
+
+	pub fn set_powerup(&mut self, powerup: Powerup) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_powerup(powerup))
+	}
+
+	pub fn switch(&mut self, on: bool) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.switch(on))
+	}
+
+	pub fn set_color(&mut self, component: Component) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_color(component))
+	}
+
+	pub fn set_color_rgb(&mut self, rgb: RGB8) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_color_rgb(rgb))
+	}
+
+	pub fn set_color_hsv(&mut self, hsv: HSV) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_color_hsv(hsv))
+	}
+
+	pub fn set_color_hex(&mut self, hex: &str) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_color_hex(hex))
+	}
+
+	pub fn set_temperature(&mut self, mirek: u32) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_temperature(mirek))
+	}
+
+	pub fn set_white(&mut self, kelvin: u32) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_white(kelvin))
+	}
+
+	pub fn set_gradient(&mut self, points: &[Component], mode: GradientMode) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_gradient(points, mode))
+	}
+
+	pub fn set_gradient_rgb(&mut self, colors: &[RGB8], mode: GradientMode) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_gradient_rgb(colors, mode))
+	}
+
+	pub fn set_gradient_palette(&mut self, palette: &Palette, mode: GradientMode) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_gradient_palette(palette, mode))
+	}
+
+	pub fn dimm(&mut self, value: f32) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.dimm(value))
+	}
+
+	pub fn set_effect(&mut self, effect: Effect) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_effect(effect))
+	}
+
+	pub fn set_timed_effect(&mut self, effect: TimedEffect, duration: Duration) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.set_timed_effect(effect, duration))
+	}
+
+	pub fn alert(&self) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.alert())
+	}
+
+	pub fn signal(&self, signal: Signal, duration: Duration, colors: &[Component]) -> Result<(), HueError> {
+		self.runtime.block_on(self.inner.signal(signal, duration, colors))
+	}
+}
+
+impl Deref for Light {
+	type Target = crate::Light;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
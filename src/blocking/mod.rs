@@ -0,0 +1,159 @@
+//! A blocking API, for applications which don't want to run a Tokio runtime themselves.
+//!
+//! Each [`Hue`] holds its own single threaded runtime, shared with the lights, devices and home it returns, and blocks
+//! on it for every call. Like [`reqwest::blocking`], these must not be used from within an async runtime, else they
+//! panic.
+//!
+//! Requires the `blocking` feature.
+//! ```no_run
+//! # use std::net::Ipv4Addr;
+//! # use huehue::blocking::Hue;
+//! # use huehue::models::device_type::DeviceType;
+//! #
+//! # fn switch(ip: Ipv4Addr, device_type: DeviceType, application_key: String) {
+//! let hue = Hue::new_with_key(ip, device_type, application_key)
+//! 	.expect("Failed to run bridge information.");
+//! for mut light in hue.lights().expect("Failed to list lights.") {
+//! 	light.switch(false).expect("Failed to switch light off.");
+//! }
+//! # }
+//! ```
+
+mod device;
+mod home;
+mod hue;
+mod light;
+
+use std::sync::Arc;
+
+pub use device::{Device, Devices};
+pub use home::Home;
+pub use hue::Hue;
+pub use light::{Light, Lights};
+use tokio::runtime::Runtime;
+
+use crate::HueError;
+
+fn runtime() -> Result<Arc<Runtime>, HueError> {
+	tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()
+		.map(Arc::new)
+		.map_err(|_| HueError::Unexpected)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::Ipv4Addr;
+
+	use super::*;
+	use crate::hue::tests::{CONFIG, DEVICE, DEVICE_ID, LIGHT, LIGHT_ID};
+	use crate::models::device_type::DeviceType;
+	use crate::transport::{MemoryTransport, Method};
+
+	const GROUPED_LIGHT_ID: &str = "3e4f5a6b-7c8d-4e9f-0a1b-2c3d4e5f6a7b";
+
+	fn data(items: &str) -> String {
+		format!(r#"{{"errors": [], "data": [{}]}}"#, items)
+	}
+
+	/// Blocking Hue connected to a bridge served by `transport`, which accepts every update of the `rtype` resource
+	/// `id`.
+	fn hue(transport: Arc<MemoryTransport>, rtype: &str, id: &str) -> Hue {
+		transport.respond(Method::Get, "/api/0/config", 200, CONFIG);
+		transport.respond(
+			Method::Put,
+			format!("/clip/v2/resource/{}/{}", rtype, id).as_str(),
+			200,
+			data(format!(r#"{{"rid": "{}", "rtype": "{}"}}"#, id, rtype).as_str()).as_str(),
+		);
+
+		let device_type = DeviceType::new("huehue".to_owned(), "test".to_owned()).unwrap();
+		Hue::new_with_transport(Ipv4Addr::LOCALHOST, device_type, Some("key".to_owned()), transport).unwrap()
+	}
+
+	#[test]
+	fn light_switch() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone(), "light", LIGHT_ID);
+		transport.respond(Method::Get, "/clip/v2/resource/light", 200, data(LIGHT).as_str());
+
+		let mut lights = hue.lights().unwrap();
+		assert_eq!(lights.len(), 1);
+		assert!(lights[0].on);
+
+		lights[0].switch(false).unwrap();
+		assert!(!lights[0].on);
+
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.method, Method::Put);
+		assert_eq!(request.url.path(), format!("/clip/v2/resource/light/{}", LIGHT_ID));
+		assert_eq!(request.body.as_deref(), Some(r#"{"on":{"on":false}}"#));
+	}
+
+	#[test]
+	fn home_switch() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone(), "grouped_light", GROUPED_LIGHT_ID);
+		transport.respond(
+			Method::Get,
+			"/clip/v2/resource/bridge",
+			200,
+			data(
+				r#"{
+					"id": "5f6a7b8c-9d0e-4f1a-2b3c-4d5e6f7a8b9c", "bridge_id": "001788fffe000000",
+					"time_zone": {"time_zone": "Europe/Amsterdam"}, "type": "bridge"
+				}"#,
+			)
+			.as_str(),
+		);
+		transport.respond(
+			Method::Get,
+			"/clip/v2/resource/bridge_home",
+			200,
+			data(
+				format!(
+					r#"{{
+						"id": "0b7a3d6e-8f3e-4b2c-9d3e-1f2a3b4c5d6e", "children": [],
+						"services": [{{"rid": "{}", "rtype": "grouped_light"}}], "type": "bridge_home"
+					}}"#,
+					GROUPED_LIGHT_ID
+				)
+				.as_str(),
+			)
+			.as_str(),
+		);
+
+		let home = hue.home().unwrap();
+		assert_eq!(home.time_zone, "Europe/Amsterdam");
+		assert_eq!(home.grouped_light, uuid::Uuid::parse_str(GROUPED_LIGHT_ID).ok());
+
+		home.switch(false).unwrap();
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.method, Method::Put);
+		assert_eq!(
+			request.url.path(),
+			format!("/clip/v2/resource/grouped_light/{}", GROUPED_LIGHT_ID)
+		);
+	}
+
+	#[test]
+	fn device_identify() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone(), "device", DEVICE_ID);
+		transport.respond(
+			Method::Get,
+			format!("/clip/v2/resource/device/{}", DEVICE_ID).as_str(),
+			200,
+			data(DEVICE).as_str(),
+		);
+
+		let device = hue.device(uuid::Uuid::parse_str(DEVICE_ID).unwrap()).unwrap();
+		assert_eq!(device.name, "Desk");
+
+		device.identify().unwrap();
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.method, Method::Put);
+		assert_eq!(request.body.as_deref(), Some(r#"{"identify":{"action":"identify"}}"#));
+	}
+}
@@ -1,40 +1,176 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::net::Ipv4Addr;
+use std::str::FromStr;
 
+use crate::discover::{DiscoveredBridge, DiscoverySource};
 use crate::models;
 
-const VERSION_MIN: &str = "1948086000";
+/// Earliest bridge software version serving the Hue API v2, 1.48.1948086000.
+const VERSION_MIN: u64 = 1948086000;
 
 #[derive(Debug, Hash, Eq, PartialEq)]
 pub enum Error {
 	Connection,
 	NotHue,
 	UnknownModel,
+	InvalidVersion,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum Model {
 	BSB001,
 	BSB002,
-	Unknown,
+	/// Hue Bridge Pro.
+	BSB003,
+	Other(String),
 }
 
+/// Bridge software version, like `1948086000`.
+///
+/// Versions are compared numerically, component by component, so `999 < 1948086000`. Missing components count as
+/// zero, so `1.2 == 1.2.0`.
+#[derive(Debug, Clone, Default)]
+pub struct SoftwareVersion(Vec<u64>);
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Bridge {
 	pub id: String,
+	pub name: String,
 	pub model: Model,
-	pub version: String,
+	pub version: SoftwareVersion,
+	pub api_version: String,
+	pub datastore_version: String,
+	pub mac: String,
 	pub address: Ipv4Addr,
+	pub factory_new: bool,
+	pub replaces_bridge_id: Option<String>,
 	pub supported: bool,
+
+	/// How the bridge was discovered, if it was.
+	pub source: Option<DiscoverySource>,
+}
+
+impl SoftwareVersion {
+	pub fn new(components: Vec<u64>) -> SoftwareVersion {
+		SoftwareVersion(components)
+	}
+
+	pub fn components(&self) -> &[u64] {
+		&self.0
+	}
+
+	/// Components without the trailing zeros, which don't change the version.
+	fn significant(&self) -> &[u64] {
+		let len = self
+			.0
+			.iter()
+			.rposition(|component| *component != 0)
+			.map_or(0, |i| i + 1);
+		&self.0[..len]
+	}
+}
+
+impl PartialEq for SoftwareVersion {
+	fn eq(&self, other: &Self) -> bool {
+		self.significant() == other.significant()
+	}
+}
+
+impl Eq for SoftwareVersion {}
+
+impl Hash for SoftwareVersion {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.significant().hash(state);
+	}
+}
+
+impl PartialOrd for SoftwareVersion {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for SoftwareVersion {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// without trailing zeros, a missing component is always lower than a present one.
+		self.significant().cmp(other.significant())
+	}
+}
+
+impl FromStr for SoftwareVersion {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.split('.')
+			.map(|component| component.parse::<u64>().map_err(|_| Error::InvalidVersion))
+			.collect::<Result<Vec<u64>, Error>>()
+			.map(SoftwareVersion)
+	}
+}
+
+impl Display for SoftwareVersion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let components: Vec<String> = self.0.iter().map(|component| component.to_string()).collect();
+		write!(f, "{}", components.join("."))
+	}
+}
+
+impl Model {
+	/// Whether the model can ever run the Hue API v2. The first generation bridge (BSB001) is no longer updated.
+	pub fn supports_clip_v2(&self) -> bool {
+		!matches!(self, Model::BSB001)
+	}
+}
+
+impl Bridge {
+	/// Whether a bridge of `model` running `version` serves the Hue API v2.
+	fn supports_clip_v2(model: &Model, version: &SoftwareVersion) -> bool {
+		model.supports_clip_v2() && *version >= SoftwareVersion::new(vec![VERSION_MIN])
+	}
 }
 
 impl From<(Ipv4Addr, models::Config)> for Bridge {
 	fn from(data: (Ipv4Addr, models::Config)) -> Self {
+		let (address, config) = data;
+		let model = Model::from(&config.modelid);
+		let version = SoftwareVersion::from_str(config.swversion.as_str()).unwrap_or_default();
+
+		Bridge {
+			supported: Self::supports_clip_v2(&model, &version),
+			id: config.bridgeid,
+			name: config.name,
+			model,
+			version,
+			api_version: config.apiversion,
+			datastore_version: config.datastoreversion,
+			mac: config.mac,
+			address,
+			factory_new: config.factorynew,
+			replaces_bridge_id: config.replacesbridgeid,
+			source: None,
+		}
+	}
+}
+
+/// Bridge with only what the discovery source reported, for when its configuration can't be read. As the software
+/// version is unknown, it is never reported as supported.
+impl From<&DiscoveredBridge> for Bridge {
+	fn from(discovered: &DiscoveredBridge) -> Self {
 		Bridge {
-			id: data.1.bridgeid,
-			model: Model::from(&data.1.modelid),
-			version: data.1.swversion.clone(),
-			address: data.0,
-			supported: data.1.swversion.as_str() >= VERSION_MIN,
+			id: discovered.id.clone().unwrap_or_default(),
+			name: String::new(),
+			model: discovered.model.clone().unwrap_or_else(|| Model::Other(String::new())),
+			version: SoftwareVersion::default(),
+			api_version: String::new(),
+			datastore_version: String::new(),
+			mac: String::new(),
+			address: discovered.address,
+			factory_new: false,
+			replaces_bridge_id: None,
+			supported: false,
+			source: Some(discovered.source),
 		}
 	}
 }
@@ -44,7 +180,62 @@ impl From<&String> for Model {
 		match value.as_str() {
 			"BSB001" => Model::BSB001,
 			"BSB002" => Model::BSB002,
-			_ => Model::Unknown,
+			"BSB003" => Model::BSB003,
+			other => Model::Other(other.to_owned()),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn software_version_order() {
+		let v = |s: &str| SoftwareVersion::from_str(s).unwrap();
+
+		assert!(v("999") < v("1948086000"));
+		assert!(v("1948086000") < v("1950111030"));
+		assert!(v("1.50.1950111030") < v("1.51.1950111030"));
+		assert_eq!(v("1948086000"), v("1948086000"));
+		assert_eq!(v("1.2"), v("1.2.0"));
+		assert_eq!(v("1.2").cmp(&v("1.2.0.0")), Ordering::Equal);
+		assert!(v("1.2") < v("1.2.1"));
+		assert!(v("1.2.0.1") > v("1.2"));
+		assert!(v("0") == SoftwareVersion::default());
+		assert!(SoftwareVersion::from_str("19480a").is_err());
+		assert_eq!(v("1.50.1950111030").to_string(), "1.50.1950111030");
+	}
+
+	#[test]
+	fn bridge_supports() {
+		let config = |modelid: &str, swversion: &str| {
+			models::Config {
+				name: "Hue Bridge".to_owned(),
+				datastoreversion: "131".to_owned(),
+				swversion: swversion.to_owned(),
+				apiversion: "1.48.0".to_owned(),
+				mac: "00:17:88:00:00:00".to_owned(),
+				bridgeid: "001788FFFE000000".to_owned(),
+				factorynew: false,
+				replacesbridgeid: None,
+				modelid: modelid.to_owned(),
+				starterkitid: None,
+			}
+		};
+		let address = Ipv4Addr::new(192, 168, 0, 2);
+
+		let bridge = Bridge::from((address, config("BSB002", "1950111030")));
+		assert!(bridge.supported);
+
+		let bridge = Bridge::from((address, config("BSB002", "999")));
+		assert!(!bridge.supported);
+
+		let bridge = Bridge::from((address, config("BSB001", "1950111030")));
+		assert!(!bridge.supported);
+
+		let bridge = Bridge::from((address, config("BSB004", "1960000000")));
+		assert_eq!(bridge.model, Model::Other("BSB004".to_owned()));
+		assert!(bridge.supported);
+	}
+}
@@ -0,0 +1,88 @@
+//! Serde representations of colors suited to configuration files, to use through `#[serde(with = "...")]`. The
+//! default representations of the color types are the ones of the Hue API.
+//!
+//! ```
+//! # use huehue::color::{Component, RGB8};
+//! # use serde::{Deserialize, Serialize};
+//! #
+//! #[derive(Serialize, Deserialize)]
+//! struct Scene {
+//! 	#[serde(with = "huehue::color::config::hex")]
+//! 	accent: RGB8,
+//! 	#[serde(with = "huehue::color::config::xy")]
+//! 	ambient: Component,
+//! }
+//!
+//! let scene: Scene =
+//! 	serde_json::from_str(r##"{"accent": "#ff8000", "ambient": [0.4, 0.4]}"##).unwrap();
+//! assert_eq!(scene.accent, RGB8::new(255, 128, 0));
+//! assert_eq!(scene.ambient, Component::unchecked(0.4, 0.4));
+//! ```
+
+/// [`RGB8`](super::RGB8) as a `#rrggbb` string. CSS color names are accepted too.
+pub mod hex {
+	use std::str::FromStr;
+
+	use serde::de::Error;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	use crate::color::RGB8;
+
+	pub fn serialize<S: Serializer>(rgb: &RGB8, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&rgb.to_hex())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<RGB8, D::Error> {
+		let hex = String::deserialize(deserializer)?;
+		RGB8::from_str(&hex).map_err(|e| D::Error::custom(format!("invalid color {:?}: {:?}", hex, e)))
+	}
+}
+
+/// [`Component`](super::Component) as an `[x, y]` array, which must be inside the CIE horseshoe.
+pub mod xy {
+	use serde::de::Error;
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	use crate::color::Component;
+
+	pub fn serialize<S: Serializer>(xy: &Component, serializer: S) -> Result<S::Ok, S::Error> {
+		[xy.x, xy.y].serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Component, D::Error> {
+		let [x, y] = <[f32; 2]>::deserialize(deserializer)?;
+		Component::try_from((x, y)).map_err(|_| D::Error::custom(format!("[{}, {}] is not a color", x, y)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::{Deserialize, Serialize};
+
+	use crate::color::{Component, RGB8};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Config {
+		#[serde(with = "super::hex")]
+		rgb: RGB8,
+		#[serde(with = "super::xy")]
+		xy: Component,
+	}
+
+	#[test]
+	fn config() {
+		let config = Config {
+			rgb: RGB8::new(255, 128, 0),
+			xy: Component::unchecked(0.25, 0.5),
+		};
+		let json = serde_json::to_string(&config).unwrap();
+		assert_eq!(json, r##"{"rgb":"#ff8000","xy":[0.25,0.5]}"##);
+		assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+		let config: Config = serde_json::from_str(r#"{"rgb": "teal", "xy": [0.3, 0.3]}"#).unwrap();
+		assert_eq!(config.rgb, RGB8::new(0, 128, 128));
+
+		assert!(serde_json::from_str::<Config>(r##"{"rgb": "#ff80", "xy": [0.3, 0.3]}"##).is_err());
+		assert!(serde_json::from_str::<Config>(r#"{"rgb": "teal", "xy": [0.1, 0.9]}"#).is_err());
+	}
+}
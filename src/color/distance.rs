@@ -0,0 +1,271 @@
+//! Perceptual distances between colors, and names of colors.
+
+use super::{Color, Component, Gamut, Lab, RGB8, XYZ};
+
+/// Formula of the color difference (ΔE) between two colors in [`Lab`]. A difference of about 1 is barely noticeable
+/// side by side.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum DeltaE {
+	/// Euclidean distance in [`Lab`], which overstates differences between saturated colors.
+	CIE76,
+	/// CIEDE2000, which corrects CIE76 for how differences of lightness, chroma and hue are perceived.
+	#[default]
+	CIEDE2000,
+}
+
+/// Common colors, as named by [`Component::describe`].
+const NAMES: [(&str, RGB8); 10] = [
+	("red", RGB8 { r: 255, g: 0, b: 0 }),
+	("orange", RGB8 { r: 255, g: 128, b: 0 }),
+	("yellow", RGB8 { r: 255, g: 255, b: 0 }),
+	("green", RGB8 { r: 0, g: 255, b: 0 }),
+	("cyan", RGB8 { r: 0, g: 255, b: 255 }),
+	("blue", RGB8 { r: 0, g: 0, b: 255 }),
+	("purple", RGB8 { r: 128, g: 0, b: 255 }),
+	("magenta", RGB8 { r: 255, g: 0, b: 255 }),
+	("pink", RGB8 { r: 255, g: 128, b: 192 }),
+	("white", RGB8 { r: 255, g: 255, b: 255 }),
+];
+
+/// Whites, by color temperature in Kelvin, as named by [`Component::describe`].
+const WHITES: [(&str, f32); 3] = [
+	("warm white", 2700.0),
+	("neutral white", 4000.0),
+	("cool white", 6500.0),
+];
+
+impl Lab {
+	/// Color difference between `self` and `other` through `formula`, including their difference of lightness.
+	pub fn delta_e(&self, other: &Lab, formula: DeltaE) -> f32 {
+		match formula {
+			DeltaE::CIE76 => {
+				((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2)).sqrt()
+			},
+			DeltaE::CIEDE2000 => ciede2000(self, other),
+		}
+	}
+}
+
+impl Component {
+	/// Color difference between `self` and `other`, at the same luminance (the one of white).
+	pub fn delta_e(&self, other: &Component, formula: DeltaE) -> f32 {
+		self.lab().delta_e(&other.lab(), formula)
+	}
+
+	fn lab(&self) -> Lab {
+		Lab::from(XYZ::from_xy(self, 1f32))
+	}
+
+	/// Name of the common color closest to `self`, like "red" or "warm white", for logs and voice assistants.
+	pub fn describe(&self) -> &'static str {
+		let lab = self.lab();
+		let whites = WHITES
+			.iter()
+			.map(|(name, kelvin)| (*name, Component::from_kelvin(*kelvin)));
+		let colors = NAMES
+			.iter()
+			.filter_map(|(name, rgb)| XYZ::from(*rgb).xy().map(|xy| (*name, xy)));
+
+		whites
+			.chain(colors)
+			.map(|(name, xy)| (name, lab.delta_e(&xy.lab(), DeltaE::CIEDE2000)))
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(name, _)| name)
+			.unwrap()
+	}
+}
+
+impl RGB8 {
+	/// Color difference between sRGB `self` and `other` through `formula`, each at its own luminance, unlike
+	/// [`Component::delta_e`].
+	pub fn delta_e(&self, other: &RGB8, formula: DeltaE) -> f32 {
+		Lab::from(XYZ::from(*self)).delta_e(&Lab::from(XYZ::from(*other)), formula)
+	}
+}
+
+impl Gamut {
+	/// Color of the gamut with the least [`DeltaE::CIEDE2000`] difference to `target`, and that difference. Colors
+	/// within the gamut are reachable as they are, with no difference.
+	///
+	/// Unlike [`Gamut::restrain`], which takes the closest point in CIE xy, this is the closest as perceived.
+	pub fn closest_reachable(&self, target: &Component) -> (Component, f32) {
+		if self.contains(target) {
+			return (*target, 0f32);
+		}
+
+		const SAMPLES: usize = 64;
+		let lab = target.lab();
+		let edges = [
+			(&self.red, &self.green),
+			(&self.green, &self.blue),
+			(&self.blue, &self.red),
+		];
+		let at = |edge: usize, t: f32| {
+			let (a, b) = edges[edge];
+			Component {
+				x: a.x + (b.x - a.x) * t,
+				y: a.y + (b.y - a.y) * t,
+			}
+		};
+		let error = |edge: usize, t: f32| lab.delta_e(&at(edge, t).lab(), DeltaE::CIEDE2000);
+
+		// finds the closest sample along the edges, then refines it between its neighbors through a golden section
+		// search.
+		let (edge, sample) = (0..edges.len())
+			.flat_map(|edge| (0..=SAMPLES).map(move |sample| (edge, sample)))
+			.min_by(|a, b| {
+				let t = |sample: usize| sample as f32 / SAMPLES as f32;
+				error(a.0, t(a.1)).total_cmp(&error(b.0, t(b.1)))
+			})
+			.unwrap();
+
+		let ratio = (5f32.sqrt() - 1f32) / 2f32;
+		let (mut low, mut high) = (
+			sample.saturating_sub(1) as f32 / SAMPLES as f32,
+			(sample + 1).min(SAMPLES) as f32 / SAMPLES as f32,
+		);
+		for _ in 0..32 {
+			let (a, b) = (high - ratio * (high - low), low + ratio * (high - low));
+			if error(edge, a) < error(edge, b) {
+				high = b;
+			} else {
+				low = a;
+			}
+		}
+
+		let t = (low + high) / 2f32;
+		(at(edge, t), error(edge, t))
+	}
+}
+
+impl Color {
+	/// Name of the common color closest to the light's color. See [`Component::describe`].
+	pub fn describe(&self) -> &'static str {
+		self.xy.describe()
+	}
+}
+
+/// The CIEDE2000 color difference, as in Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula".
+fn ciede2000(lab1: &Lab, lab2: &Lab) -> f32 {
+	let (l1, a1, b1) = (lab1.l as f64, lab1.a as f64, lab1.b as f64);
+	let (l2, a2, b2) = (lab2.l as f64, lab2.a as f64, lab2.b as f64);
+	let pow7 = |c: f64| c.powi(7);
+	let hue = |b: f64, a: f64| {
+		if a == 0.0 && b == 0.0 {
+			0.0
+		} else {
+			b.atan2(a).to_degrees().rem_euclid(360.0)
+		}
+	};
+
+	let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+	let g = 0.5 * (1.0 - (pow7(c_mean) / (pow7(c_mean) + pow7(25.0))).sqrt());
+	let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+	let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+	let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+	let delta_l = l2 - l1;
+	let delta_c = c2 - c1;
+	let delta_h = if c1 * c2 == 0.0 {
+		0.0
+	} else if (h2 - h1).abs() <= 180.0 {
+		h2 - h1
+	} else if h2 <= h1 {
+		h2 - h1 + 360.0
+	} else {
+		h2 - h1 - 360.0
+	};
+	let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+	let l_mean = (l1 + l2) / 2.0;
+	let c_mean = (c1 + c2) / 2.0;
+	let h_mean = if c1 * c2 == 0.0 {
+		h1 + h2
+	} else if (h1 - h2).abs() <= 180.0 {
+		(h1 + h2) / 2.0
+	} else if h1 + h2 < 360.0 {
+		(h1 + h2 + 360.0) / 2.0
+	} else {
+		(h1 + h2 - 360.0) / 2.0
+	};
+
+	let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+		+ 0.24 * (2.0 * h_mean).to_radians().cos()
+		+ 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+		- 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+	let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+	let r_c = 2.0 * (pow7(c_mean) / (pow7(c_mean) + pow7(25.0))).sqrt();
+	let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+	let s_c = 1.0 + 0.045 * c_mean;
+	let s_h = 1.0 + 0.015 * c_mean * t;
+	let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+	let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+	(l * l + c * c + h * h + r_t * c * h).sqrt() as f32
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn delta_e() {
+		// pairs of the test data of Sharma, Wu and Dalal.
+		for (a, b, expected) in [
+			((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+			((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+			((50.0, 2.49, -0.001), (50.0, -2.49, 0.0011), 7.2195),
+			((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+			((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+			((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+		] {
+			let (a, b) = (Lab::new(a.0, a.1, a.2), Lab::new(b.0, b.1, b.2));
+			assert!((a.delta_e(&b, DeltaE::CIEDE2000) - expected).abs() < 0.0001);
+			assert!((b.delta_e(&a, DeltaE::CIEDE2000) - expected).abs() < 0.0001);
+		}
+
+		let (a, b) = (Lab::new(50.0, 2.6772, -79.7751), Lab::new(50.0, 0.0, -82.7485));
+		assert!((a.delta_e(&b, DeltaE::CIE76) - 4.0011).abs() < 0.0001);
+
+		assert_eq!(
+			RGB8::new(10, 20, 30).delta_e(&RGB8::new(10, 20, 30), DeltaE::CIEDE2000),
+			0.0
+		);
+		let (red, orange) = (RGB8::new(255, 0, 0), RGB8::new(255, 128, 0));
+		assert!(red.delta_e(&orange, DeltaE::CIEDE2000) < red.delta_e(&orange, DeltaE::CIE76));
+		assert!(Component::WHITE.delta_e(&Component::unchecked(0.32, 0.33), DeltaE::CIEDE2000) > 0.0);
+	}
+
+	#[test]
+	fn closest_reachable() {
+		let inside = Component::unchecked(0.4, 0.4);
+		let (xy, error) = Gamut::C.closest_reachable(&inside);
+		assert_eq!((xy.x, xy.y, error), (inside.x, inside.y, 0.0));
+
+		// closer than the nearest point in CIE xy, or any other point of the edge.
+		let target = Component::unchecked(0.1, 0.5);
+		let (xy, error) = Gamut::A.closest_reachable(&target);
+		assert!((error - target.delta_e(&xy, DeltaE::CIEDE2000)).abs() < 0.0001);
+		assert!(error <= target.delta_e(&Gamut::A.restrain(&target), DeltaE::CIEDE2000));
+		for t in 0..=100 {
+			let t = t as f32 / 100.0;
+			let edge = Component::unchecked(
+				Gamut::A.green.x + (Gamut::A.blue.x - Gamut::A.green.x) * t,
+				Gamut::A.green.y + (Gamut::A.blue.y - Gamut::A.green.y) * t,
+			);
+			assert!(error <= target.delta_e(&edge, DeltaE::CIEDE2000) + 0.0001);
+		}
+	}
+
+	#[test]
+	fn describe() {
+		assert_eq!(Gamut::C.xy_from_rgb8(RGB8::new(255, 0, 0)).describe(), "red");
+		assert_eq!(Gamut::C.xy_from_rgb8(RGB8::new(0, 0, 200)).describe(), "blue");
+		assert_eq!(Gamut::C.xy_from_rgb8(RGB8::new(255, 200, 0)).describe(), "yellow");
+		assert_eq!(Component::from_kelvin(2500.0).describe(), "warm white");
+		assert_eq!(Component::from_kelvin(7000.0).describe(), "cool white");
+
+		let color = Color::new(Component::unchecked(0.2, 0.6), Gamut::C).unwrap();
+		assert_eq!(color.describe(), "green");
+	}
+}
@@ -0,0 +1,106 @@
+//! The CIE 1931 chromaticity diagram (the "horseshoe"), which bounds every color that can be seen.
+
+use super::Component;
+
+/// Chromaticities of monochromatic light, every 5nm from 380nm to 700nm, for the CIE 1931 2° standard observer. The
+/// line between both ends bounds the purples.
+const SPECTRAL_LOCUS: [(f32, f32); 65] = [
+	(0.1741, 0.005),
+	(0.174, 0.005),
+	(0.1738, 0.0049),
+	(0.1736, 0.0049),
+	(0.1733, 0.0048),
+	(0.173, 0.0048),
+	(0.1726, 0.0048),
+	(0.1721, 0.0048),
+	(0.1714, 0.0051),
+	(0.1703, 0.0058),
+	(0.1689, 0.0069),
+	(0.1669, 0.0086),
+	(0.1644, 0.0109),
+	(0.1611, 0.0138),
+	(0.1566, 0.0177),
+	(0.151, 0.0227),
+	(0.144, 0.0297),
+	(0.1355, 0.0399),
+	(0.1241, 0.0578),
+	(0.1096, 0.0868),
+	(0.0913, 0.1327),
+	(0.0687, 0.2007),
+	(0.0454, 0.295),
+	(0.0235, 0.4127),
+	(0.0082, 0.5384),
+	(0.0039, 0.6548),
+	(0.0139, 0.7502),
+	(0.0389, 0.812),
+	(0.0743, 0.8338),
+	(0.1142, 0.8262),
+	(0.1547, 0.8059),
+	(0.1929, 0.7816),
+	(0.2296, 0.7543),
+	(0.2658, 0.7243),
+	(0.3016, 0.6923),
+	(0.3373, 0.6589),
+	(0.3731, 0.6245),
+	(0.4087, 0.5896),
+	(0.4441, 0.5547),
+	(0.4788, 0.5202),
+	(0.5125, 0.4866),
+	(0.5448, 0.4544),
+	(0.5752, 0.4242),
+	(0.6029, 0.3965),
+	(0.627, 0.3725),
+	(0.6482, 0.3514),
+	(0.6658, 0.334),
+	(0.6801, 0.3197),
+	(0.6915, 0.3083),
+	(0.7006, 0.2993),
+	(0.7079, 0.292),
+	(0.714, 0.2859),
+	(0.719, 0.2809),
+	(0.723, 0.277),
+	(0.726, 0.274),
+	(0.7283, 0.2717),
+	(0.73, 0.27),
+	(0.7311, 0.2689),
+	(0.732, 0.268),
+	(0.7327, 0.2673),
+	(0.7334, 0.2666),
+	(0.734, 0.266),
+	(0.7344, 0.2656),
+	(0.7346, 0.2654),
+	(0.7347, 0.2653),
+];
+
+/// How far out of the horseshoe colors may be, so the primaries of gamuts on its edge (like [`Gamut::C`]'s red) and
+/// rounding errors are accepted.
+///
+/// [`Gamut::C`]: super::Gamut::C
+const EPSILON: f32 = 0.001;
+
+/// Whether `xy` is a real color, inside (or on) the horseshoe.
+pub(super) fn contains(xy: &Component) -> bool {
+	if !(xy.x.is_finite() && xy.y.is_finite()) {
+		return false;
+	}
+
+	let edges = || SPECTRAL_LOCUS.iter().zip(SPECTRAL_LOCUS.iter().cycle().skip(1));
+
+	// even-odd rule, as the locus isn't quite convex at its ends.
+	let crossings = edges()
+		.filter(|((ax, ay), (bx, by))| (*ay > xy.y) != (*by > xy.y) && xy.x < ax + (xy.y - ay) * (bx - ax) / (by - ay))
+		.count();
+
+	crossings % 2 == 1 || edges().any(|(a, b)| distance2_to_segment(xy, *a, *b) <= EPSILON * EPSILON)
+}
+
+fn distance2_to_segment(p: &Component, (ax, ay): (f32, f32), (bx, by): (f32, f32)) -> f32 {
+	let d2 = (bx - ax).powi(2) + (by - ay).powi(2);
+	let t = if d2 > 0f32 {
+		(((p.x - ax) * (bx - ax) + (p.y - ay) * (by - ay)) / d2).clamp(0f32, 1f32)
+	} else {
+		0f32
+	};
+
+	(p.x - (ax + t * (bx - ax))).powi(2) + (p.y - (ay + t * (by - ay))).powi(2)
+}
@@ -0,0 +1,195 @@
+//! Mapping of colors out of a gamut into it.
+
+use super::{Component, Gamut, OKLab, XYZ};
+
+/// How [`Gamut::map`] brings colors out of the gamut into it.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum GamutMapping {
+	/// Closest point of the gamut, as [`Gamut::restrain`]. Keeps colors as close as possible, but shifts the hue of
+	/// saturated colors far out of the gamut.
+	#[default]
+	Nearest,
+	/// Point of the gamut's edge on the way to the white point, which keeps the hue (in OKLab, as lines to the white
+	/// point in CIE xy shift blues toward purple) and gives up saturation.
+	HuePreserving,
+	/// Like [`GamutMapping::HuePreserving`], but compresses the saturation of colors close to the edge too, so
+	/// different colors out of the gamut remain different rather than all clipped to its edge. Colors well within the
+	/// gamut are left as they are.
+	ChromaCompression,
+}
+
+impl Gamut {
+	/// Share of the chroma of the edge of the gamut, at each hue, below which [`GamutMapping::ChromaCompression`]
+	/// leaves colors as they are.
+	const COMPRESSION_KNEE: f32 = 0.8;
+
+	/// Maps `xy` into the gamut through `strategy`.
+	pub fn map(&self, xy: &Component, strategy: GamutMapping) -> Component {
+		match strategy {
+			GamutMapping::Nearest => self.restrain(xy),
+			GamutMapping::HuePreserving if self.contains(xy) => *xy,
+			GamutMapping::HuePreserving => self.toward_white(xy, |_| 1f32),
+			GamutMapping::ChromaCompression => {
+				self.toward_white(xy, |ratio| {
+					const KNEE: f32 = Gamut::COMPRESSION_KNEE;
+
+					if ratio <= KNEE {
+						ratio
+					} else {
+						// smoothly approaches the edge, with no sudden change of slope at the knee.
+						KNEE + (1f32 - KNEE) * ((ratio - KNEE) / (1f32 - KNEE)).tanh()
+					}
+				})
+			},
+		}
+	}
+
+	/// The D65 white point, or the center of the gamut if it doesn't contain it (like [`Gamut::B`]).
+	pub fn white(&self) -> Component {
+		if self.contains(&Component::WHITE) {
+			Component::WHITE
+		} else {
+			(self.red + self.green + self.blue) / 3f32
+		}
+	}
+
+	/// Moves `xy` toward the white point, keeping its OKLab lightness and hue, to `compress` of the ratio between its
+	/// chroma and the one of the edge of the gamut at that hue. Ratios above 1 are out of the gamut.
+	fn toward_white(&self, xy: &Component, compress: impl Fn(f32) -> f32) -> Component {
+		let white = self.white();
+		let (lab, white_lab) = (
+			OKLab::from(XYZ::from_xy(xy, 1f32)),
+			OKLab::from(XYZ::from_xy(&white, 1f32)),
+		);
+		let at = |t: f32| {
+			XYZ::from(OKLab::new(
+				lab.l,
+				white_lab.a + (lab.a - white_lab.a) * t,
+				white_lab.b + (lab.b - white_lab.b) * t,
+			))
+			.xy()
+		};
+		let inside = |t: f32| at(t).is_some_and(|xy| self.contains(&xy));
+
+		// bounds the edge, then bisects it.
+		let (mut low, mut high) = (0f32, 1f32);
+		while inside(high) && high < 64f32 {
+			low = high;
+			high *= 2f32;
+		}
+		for _ in 0..24 {
+			let middle = (low + high) / 2f32;
+			if inside(middle) {
+				low = middle;
+			} else {
+				high = middle;
+			}
+		}
+		if low <= 0f32 {
+			return self.restrain(&white);
+		}
+
+		let t = compress(1f32 / low).min(1f32) * low;
+		self.restrain(&at(t).unwrap_or(white))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert_component_eq;
+	use crate::color::RgbSpace;
+
+	/// Hue, in degrees, of `xy` at the same luminance.
+	fn hue(xy: &Component) -> f32 {
+		let lab = OKLab::from(XYZ::from_xy(xy, 0.5));
+		lab.b.atan2(lab.a).to_degrees()
+	}
+
+	fn hue_deviation(a: &Component, b: &Component) -> f32 {
+		let deviation = (hue(a) - hue(b)).rem_euclid(360f32);
+		deviation.min(360f32 - deviation)
+	}
+
+	/// Saturated colors of Rec.2020, mostly out of the Hue gamuts: its primaries, and mixes of each pair of them.
+	fn saturated() -> Vec<Component> {
+		let gamut = RgbSpace::REC_2020.gamut();
+		let primaries = [gamut.red, gamut.green, gamut.blue];
+
+		let mut colors = Vec::new();
+		for (i, a) in primaries.iter().enumerate() {
+			let b = &primaries[(i + 1) % 3];
+			for t in [0f32, 0.25, 0.5, 0.75] {
+				colors.push(Component::unchecked(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t));
+			}
+		}
+		colors
+	}
+
+	/// Mapping toward the white point keeps hues within 0.01 degree, while the nearest point shifts them by degrees.
+	#[test]
+	fn map_hue_deviation() {
+		// gamut B doesn't contain the white point, so it can't reach every hue.
+		for gamut in [Gamut::A, Gamut::C] {
+			let deviation = |strategy: GamutMapping| -> (f32, f32) {
+				let deviations: Vec<f32> = saturated()
+					.iter()
+					.map(|xy| {
+						let mapped = gamut.map(xy, strategy);
+						assert_component_eq!(mapped, gamut.restrain(&mapped), 0.0001);
+						hue_deviation(xy, &mapped)
+					})
+					.collect();
+				let mean = deviations.iter().sum::<f32>() / deviations.len() as f32;
+				(mean, deviations.into_iter().fold(0f32, f32::max))
+			};
+
+			let (nearest, nearest_max) = deviation(GamutMapping::Nearest);
+			let (_, preserving_max) = deviation(GamutMapping::HuePreserving);
+			let (_, compression_max) = deviation(GamutMapping::ChromaCompression);
+
+			assert!(nearest > 1f32 && nearest_max > 2f32, "{} {}", nearest, nearest_max);
+			assert!(preserving_max < 0.01, "{}", preserving_max);
+			assert!(compression_max < 0.01, "{}", compression_max);
+		}
+	}
+
+	#[test]
+	fn map_inside() {
+		let gamut = Gamut::C;
+		let xy = Component::unchecked(0.4, 0.4);
+
+		assert_component_eq!(xy, gamut.map(&xy, GamutMapping::Nearest), 0.0001);
+		assert_component_eq!(xy, gamut.map(&xy, GamutMapping::HuePreserving), 0.0001);
+		assert_component_eq!(
+			Component::WHITE,
+			gamut.map(&Component::WHITE, GamutMapping::ChromaCompression),
+			0.0001
+		);
+
+		// only colors close to the edge are compressed, and the ones out of the gamut stay distinct.
+		let white = gamut.white();
+		let toward = |t: f32| {
+			Component::unchecked(
+				white.x + (gamut.red.x - white.x) * t,
+				white.y + (gamut.red.y - white.y) * t,
+			)
+		};
+		assert_component_eq!(
+			toward(0.5),
+			gamut.map(&toward(0.5), GamutMapping::ChromaCompression),
+			0.0001
+		);
+		let (a, b) = (
+			gamut.map(&toward(1.1), GamutMapping::ChromaCompression),
+			gamut.map(&toward(1.2), GamutMapping::ChromaCompression),
+		);
+		assert!(a.distance2(&b) > 0f32);
+		assert!(gamut.contains(&a) && gamut.contains(&b));
+
+		for xy in saturated() {
+			let mapped = Gamut::B.map(&xy, GamutMapping::HuePreserving);
+			assert_component_eq!(mapped, Gamut::B.restrain(&mapped), 0.0001);
+		}
+	}
+}
@@ -0,0 +1,725 @@
+pub mod config;
+mod css;
+mod distance;
+mod locus;
+mod mapping;
+mod palette;
+mod rgb;
+mod space;
+mod temperature;
+
+use std::ops::{Add, Div, Mul, Sub};
+
+pub use css::ParseColorError;
+pub use distance::DeltaE;
+pub use mapping::GamutMapping;
+pub use palette::{Interpolation, Palette};
+pub use rgb::{RGBf32, RgbSpace, RGB16};
+use serde::{Deserialize, Serialize};
+pub use space::{LCh, Lab, OKLab, HSL, HSV, XYZ};
+pub use temperature::{kelvin_from_mirek, mirek_from_kelvin, KELVIN_MAX, KELVIN_MIN};
+
+/// Color in the [CIE 1931 color space](https://en.wikipedia.org/wiki/CIE_1931_color_space), without brightness.
+///
+/// Defaults to [`Component::WHITE`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Component {
+	pub x: f32,
+	pub y: f32,
+}
+
+/// Colors reachable by a light, within the triangle of its primaries.
+///
+/// Defaults to [`Gamut::C`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Gamut {
+	pub red: Component,
+	pub green: Component,
+	pub blue: Component,
+}
+
+/// Families of gamuts of Hue color lights.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum GamutType {
+	A,
+	B,
+	C,
+	#[serde(rename = "other", other)]
+	Other,
+}
+
+/// Color of a light.
+///
+/// Lights which report no gamut get the one of their [`GamutType`], or [`Gamut::C`] if it is [`GamutType::Other`]. A
+/// better guess can be made through [`Gamut::from_model_id`], with the model of the light's owner device.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ColorPayload")]
+pub struct Color {
+	pub gamut: Gamut,
+	pub gamut_type: GamutType,
+	pub xy: Component,
+}
+
+#[derive(Deserialize)]
+struct ColorPayload {
+	gamut: Option<Gamut>,
+	gamut_type: GamutType,
+	xy: Component,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirekSchema {
+	pub mirek_maximum: u32,
+	pub mirek_minimum: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Temperature {
+	pub mirek: Option<u32>,
+	pub mirek_schema: MirekSchema,
+	pub mirek_valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradientPointColor {
+	pub xy: Component,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradientPoint {
+	pub color: GradientPointColor,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientMode {
+	InterpolatedPalette,
+	InterpolatedPaletteMirrored,
+	RandomPixelated,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gradient {
+	pub points: Vec<GradientPoint>,
+	pub points_capable: usize,
+	pub mode: Option<GradientMode>,
+	pub mode_values: Option<Vec<GradientMode>>,
+	pub pixel_count: Option<u32>,
+}
+
+/// sRGB with 8 bits per channel.
+///
+/// Defaults to black, like the other RGB types.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RGB8 {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+}
+
+impl RGB8 {
+	pub fn new(r: u8, g: u8, b: u8) -> RGB8 {
+		RGB8 { r, g, b }
+	}
+}
+
+impl Component {
+	/// D65 white point, the white of sRGB.
+	pub const WHITE: Component = Component { x: 0.3127, y: 0.329 };
+
+	/// Color at `x` and `y`, if it is inside the CIE horseshoe: one that can be seen.
+	pub fn new(x: f32, y: f32) -> Option<Component> {
+		let xy = Component { x, y };
+		locus::contains(&xy).then_some(xy)
+	}
+
+	/// Color at `x` and `y`, which may not be inside the CIE horseshoe, like for intermediate results or constants.
+	pub const fn unchecked(x: f32, y: f32) -> Component {
+		Component { x, y }
+	}
+
+	/// Whether the color is inside the CIE horseshoe. See [`Component::new`].
+	pub fn is_valid(&self) -> bool {
+		locus::contains(self)
+	}
+
+	pub fn distance2(&self, p: &Component) -> f32 {
+		(self.x - p.x).powf(2.0) + (self.y - p.y).powf(2.0)
+	}
+}
+
+impl Default for Component {
+	fn default() -> Self {
+		Component::WHITE
+	}
+}
+
+/// Error of [`Component::try_from`], when the color isn't inside the CIE horseshoe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidComponent {
+	pub x: f32,
+	pub y: f32,
+}
+
+impl TryFrom<(f32, f32)> for Component {
+	type Error = InvalidComponent;
+
+	fn try_from((x, y): (f32, f32)) -> Result<Self, Self::Error> {
+		Component::new(x, y).ok_or(InvalidComponent { x, y })
+	}
+}
+
+impl From<Component> for (f32, f32) {
+	fn from(xy: Component) -> Self {
+		(xy.x, xy.y)
+	}
+}
+
+impl Add for Component {
+	type Output = Component;
+
+	fn add(self, other: Component) -> Component {
+		Component::unchecked(self.x + other.x, self.y + other.y)
+	}
+}
+
+impl Sub for Component {
+	type Output = Component;
+
+	fn sub(self, other: Component) -> Component {
+		Component::unchecked(self.x - other.x, self.y - other.y)
+	}
+}
+
+impl Mul<f32> for Component {
+	type Output = Component;
+
+	fn mul(self, factor: f32) -> Component {
+		Component::unchecked(self.x * factor, self.y * factor)
+	}
+}
+
+impl Div<f32> for Component {
+	type Output = Component;
+
+	fn div(self, divisor: f32) -> Component {
+		Component::unchecked(self.x / divisor, self.y / divisor)
+	}
+}
+
+impl GamutType {
+	/// Gamut of Hue color lights of model `model_id` (as in
+	/// [`ProductData::model_id`](crate::models::generic::ProductData::model_id)), if known.
+	pub fn from_model_id(model_id: &str) -> Option<GamutType> {
+		const A: [&str; 9] = [
+			"LST001", "LLC005", "LLC006", "LLC007", "LLC010", "LLC011", "LLC012", "LLC013", "LLC014",
+		];
+		const B: [&str; 5] = ["LCT001", "LCT002", "LCT003", "LCT007", "LLM001"];
+		const C: [&str; 11] = [
+			"LCT010", "LCT011", "LCT012", "LCT014", "LCT015", "LCT016", "LCT024", "LLC020", "LST002", "LST003",
+			"LST004",
+		];
+		/// Models of every newer Hue color light start with these.
+		const C_PREFIXES: [&str; 5] = ["LCA", "LCB", "LCE", "LCG", "LCX"];
+
+		if A.contains(&model_id) {
+			Some(GamutType::A)
+		} else if B.contains(&model_id) {
+			Some(GamutType::B)
+		} else if C.contains(&model_id) || C_PREFIXES.iter().any(|prefix| model_id.starts_with(prefix)) {
+			Some(GamutType::C)
+		} else {
+			None
+		}
+	}
+
+	/// Type of `gamut`, if it is one of the Hue gamuts.
+	pub fn of(gamut: &Gamut) -> GamutType {
+		[GamutType::A, GamutType::B, GamutType::C]
+			.into_iter()
+			.find(|gamut_type| gamut_type.gamut().is_some_and(|preset| preset.approx_eq(gamut)))
+			.unwrap_or(GamutType::Other)
+	}
+
+	pub fn gamut(&self) -> Option<Gamut> {
+		match self {
+			GamutType::A => Some(Gamut::A),
+			GamutType::B => Some(Gamut::B),
+			GamutType::C => Some(Gamut::C),
+			GamutType::Other => None,
+		}
+	}
+}
+
+impl Gamut {
+	/// Gamut of the first Hue LivingColors, Bloom, Iris and lightstrips.
+	pub const A: Gamut = Gamut {
+		red: Component { x: 0.704, y: 0.296 },
+		green: Component { x: 0.2151, y: 0.7106 },
+		blue: Component { x: 0.138, y: 0.08 },
+	};
+	/// Gamut of the first Hue color bulbs.
+	pub const B: Gamut = Gamut {
+		red: Component { x: 0.675, y: 0.322 },
+		green: Component { x: 0.409, y: 0.518 },
+		blue: Component { x: 0.167, y: 0.04 },
+	};
+	/// Gamut of most Hue color lights.
+	pub const C: Gamut = Gamut {
+		red: Component { x: 0.6915, y: 0.3083 },
+		green: Component { x: 0.17, y: 0.7 },
+		blue: Component { x: 0.1532, y: 0.0475 },
+	};
+
+	/// Gamut of Hue color lights of model `model_id`, if known. See [`GamutType::from_model_id`].
+	pub fn from_model_id(model_id: &str) -> Option<Gamut> {
+		GamutType::from_model_id(model_id).and_then(|gamut_type| gamut_type.gamut())
+	}
+
+	pub fn new(red: Component, green: Component, blue: Component) -> Gamut {
+		Gamut { red, green, blue }
+	}
+
+	fn approx_eq(&self, other: &Gamut) -> bool {
+		const EPSILON: f32 = 0.0001 * 0.0001;
+
+		self.red.distance2(&other.red) < EPSILON
+			&& self.green.distance2(&other.green) < EPSILON
+			&& self.blue.distance2(&other.blue) < EPSILON
+	}
+
+	pub fn contains(&self, xy: &Component) -> bool {
+		let s = (self.red.x - self.blue.x) * (xy.y - self.blue.y) - (self.red.y - self.blue.y) * (xy.x - self.blue.x);
+		let t = (self.green.x - self.red.x) * (xy.y - self.red.y) - (self.green.y - self.red.y) * (xy.x - self.red.x);
+
+		if s != 0f32 && t != 0f32 && ((s < 0f32 && t >= 0f32) || (s >= 0f32 && t < 0f32)) {
+			return false;
+		}
+
+		let d =
+			(self.blue.x - self.green.x) * (xy.y - self.green.y) - (self.blue.y - self.green.y) * (xy.x - self.green.x);
+		(d == 0f32) || (d < 0f32 && s + t <= 0f32) || (d >= 0f32 && s + t > 0f32)
+	}
+
+	pub fn restrain(&self, xy: &Component) -> Component {
+		if self.contains(xy) {
+			*xy
+		} else {
+			let rg = Self::restrain_point_in_segment(xy, &self.red, &self.green);
+			let gb = Self::restrain_point_in_segment(xy, &self.green, &self.blue);
+			let br = Self::restrain_point_in_segment(xy, &self.blue, &self.red);
+
+			let drg = rg.distance2(xy);
+			let dgb = gb.distance2(xy);
+			let dbr = br.distance2(xy);
+
+			if drg <= dgb && drg <= dbr {
+				rg
+			} else if dgb <= dbr && dgb <= drg {
+				gb
+			} else {
+				br
+			}
+		}
+	}
+
+	fn restrain_point_in_segment(p: &Component, a: &Component, b: &Component) -> Component {
+		let d2 = (a.x - b.x).powf(2.0) + (a.y - b.y).powf(2.0);
+		let t = (0.0f32).max((1.0f32).min(((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y)) / d2));
+		Component::unchecked(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y))
+	}
+
+	pub fn xy_from_rgb8(&self, rgb: RGB8) -> Component {
+		self.xy_from_rgb8_with_brightness(rgb).0
+	}
+
+	/// Converts sRGB `rgb` to a color restrained to the gamut, and a brightness percentage, as taken by
+	/// [`Light::dimm`](crate::Light::dimm).
+	///
+	/// The brightness is the one of the brightest channel, so fully saturated colors are at full brightness. Black has
+	/// the white point as color.
+	pub fn xy_from_rgb8_with_brightness(&self, rgb: RGB8) -> (Component, f32) {
+		self.xy_from_rgb(RGBf32::from(rgb), &RgbSpace::SRGB)
+	}
+
+	/// Like [`Gamut::xy_from_rgb8_with_brightness`], for `rgb` in any RGB `space`. Channels are clamped between 0 and
+	/// 1.
+	pub fn xy_from_rgb(&self, rgb: RGBf32, space: &RgbSpace) -> (Component, f32) {
+		let rgb = RGBf32::new(
+			rgb.r.clamp(0f32, 1f32),
+			rgb.g.clamp(0f32, 1f32),
+			rgb.b.clamp(0f32, 1f32),
+		);
+		let brightness = rgb.r.max(rgb.g).max(rgb.b) * 100f32;
+
+		let xyz = space.to_xyz(rgb);
+		if xyz.y <= 0f32 {
+			return (self.restrain(&Component::WHITE), 0f32);
+		}
+
+		// Unchecked, as rounding may put saturated colors of wide spaces just outside the horseshoe.
+		let sum = xyz.x + xyz.y + xyz.z;
+		(
+			self.restrain(&Component::unchecked(xyz.x / sum, xyz.y / sum)),
+			brightness,
+		)
+	}
+
+	/// Converts `xy`, restrained to the gamut, to RGB with a luminance equal to its `y`.
+	pub fn xy_to_rgb8(&self, xy: &Component) -> RGB8 {
+		let gxy = self.restrain(xy);
+
+		RGB8::from(Self::rgb_from_xyz(&XYZ::from_xy(&gxy, gxy.y), 1f64, &RgbSpace::SRGB))
+	}
+
+	/// Converts `xy`, restrained to the gamut, at a `brightness` percentage (like
+	/// [`Light::brightness`](crate::Light::brightness)) to RGB. The inverse of
+	/// [`Gamut::xy_from_rgb8_with_brightness`].
+	pub fn xy_to_rgb8_with_brightness(&self, xy: &Component, brightness: f32) -> RGB8 {
+		RGB8::from(self.xy_to_rgb(xy, brightness, &RgbSpace::SRGB))
+	}
+
+	/// Like [`Gamut::xy_to_rgb8_with_brightness`], to any RGB `space`. The inverse of [`Gamut::xy_from_rgb`].
+	pub fn xy_to_rgb(&self, xy: &Component, brightness: f32, space: &RgbSpace) -> RGBf32 {
+		let gxy = self.restrain(xy);
+		let brightness = space.decode((brightness.clamp(0f32, 100f32) / 100f32) as f64);
+
+		Self::rgb_from_xyz(&XYZ::from_xy(&gxy, 1f32), brightness, space)
+	}
+
+	/// Converts CIE XYZ to `space`, scaled so its brightest channel is at most `max` (in linear RGB).
+	fn rgb_from_xyz(xyz: &XYZ, max: f64, space: &RgbSpace) -> RGBf32 {
+		let linear = space.xyz_to_linear(xyz);
+
+		let brightest = linear[0].max(linear[1]).max(linear[2]);
+		let scale = if brightest > max { max / brightest } else { 1f64 };
+		let [r, g, b] = linear.map(|c| space.encode((c * scale).clamp(0f64, 1f64)) as f32);
+
+		RGBf32::new(r, g, b)
+	}
+}
+
+impl Default for Gamut {
+	fn default() -> Self {
+		Gamut::C
+	}
+}
+
+impl GradientPoint {
+	pub fn new(xy: Component) -> GradientPoint {
+		GradientPoint {
+			color: GradientPointColor { xy },
+		}
+	}
+}
+
+impl Gradient {
+	/// Minimum amount of points accepted by the bridge in a gradient.
+	pub const POINTS_MIN: usize = 2;
+
+	pub fn supports(&self, mode: GradientMode) -> bool {
+		match &self.mode_values {
+			Some(mode_values) => mode_values.contains(&mode),
+			None => mode == GradientMode::InterpolatedPalette,
+		}
+	}
+}
+
+impl Color {
+	pub fn new(xy: Component, gamut: Gamut) -> Option<Color> {
+		match gamut.contains(&xy) {
+			true => {
+				Some(Color {
+					xy,
+					gamut_type: GamutType::of(&gamut),
+					gamut,
+				})
+			},
+			false => None,
+		}
+	}
+}
+
+impl From<ColorPayload> for Color {
+	fn from(payload: ColorPayload) -> Self {
+		let gamut = payload.gamut.or_else(|| payload.gamut_type.gamut()).unwrap_or(Gamut::C);
+
+		Color {
+			gamut,
+			gamut_type: payload.gamut_type,
+			xy: payload.xy,
+		}
+	}
+}
+
+#[macro_export]
+macro_rules! assert_component_eq {
+	($a:expr, $b:expr, $d:expr) => {
+		assert!(
+			!(($a.x - $b.x > $d) || ($b.x - $a.x > $d)),
+			"x component {} and {} outside of range {} ({})",
+			$a.x,
+			$b.x,
+			$d,
+			($a.x - $b.x).abs()
+		);
+		assert!(
+			!(($a.y - $b.y > $d) || ($b.y - $a.y > $d)),
+			"y component {} and {} outside of range {} ({})",
+			$a.y,
+			$b.y,
+			$d,
+			($a.y - $b.y).abs()
+		);
+	};
+}
+
+#[macro_export]
+macro_rules! assert_rgb_eq {
+	($a:expr, $b:expr, $d:expr) => {
+		assert!(
+			!(($a.r as i16 - $b.r as i16).abs() > $d),
+			"r component {} and {} outside of range {} ({})",
+			$a.r,
+			$b.r,
+			$d,
+			($a.r as i16 - $b.r as i16).abs()
+		);
+		assert!(
+			!(($a.g as i16 - $b.g as i16).abs() > $d),
+			"g component {} and {} outside of range {} ({})",
+			$a.g,
+			$b.g,
+			$d,
+			($a.g as i16 - $b.g as i16).abs()
+		);
+		assert!(
+			!(($a.b as i16 - $b.b as i16).abs() > $d),
+			"b component {} and {} outside of range {} ({})",
+			$a.b,
+			$b.b,
+			$d,
+			($a.b as i16 - $b.b as i16).abs()
+		);
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn gradient_deserialize() {
+		let gradient: Gradient = serde_json::from_str(
+			r#"{
+				"points": [
+					{"color": {"xy": {"x": 0.6, "y": 0.3}}},
+					{"color": {"xy": {"x": 0.2, "y": 0.6}}}
+				],
+				"points_capable": 5,
+				"mode": "interpolated_palette",
+				"mode_values": ["interpolated_palette", "interpolated_palette_mirrored", "random_pixelated"],
+				"pixel_count": 7
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(gradient.points.len(), 2);
+		assert_component_eq!(Component::unchecked(0.2, 0.6), gradient.points[1].color.xy, 0.0001);
+		assert_eq!(gradient.points_capable, 5);
+		assert_eq!(gradient.mode, Some(GradientMode::InterpolatedPalette));
+		assert!(gradient.supports(GradientMode::RandomPixelated));
+		assert_eq!(gradient.pixel_count, Some(7));
+	}
+
+	#[test]
+	fn gamut_type() {
+		assert_eq!(GamutType::of(&Gamut::A), GamutType::A);
+		assert_eq!(GamutType::of(&Gamut::C), GamutType::C);
+		assert_eq!(
+			GamutType::of(&Gamut::new(
+				Component::unchecked(0.64, 0.33),
+				Component::unchecked(0.3, 0.6),
+				Component::unchecked(0.15, 0.06)
+			)),
+			GamutType::Other
+		);
+		assert_eq!(
+			Color::new(Component::unchecked(0.4, 0.35), Gamut::B)
+				.unwrap()
+				.gamut_type,
+			GamutType::B
+		);
+
+		assert_eq!(GamutType::from_model_id("LCT001"), Some(GamutType::B));
+		assert_eq!(GamutType::from_model_id("LCA001"), Some(GamutType::C));
+		assert_eq!(GamutType::from_model_id("LST001"), Some(GamutType::A));
+		assert!(Gamut::from_model_id("LWB010").is_none());
+
+		let color: Color = serde_json::from_str(r#"{"xy": {"x": 0.3, "y": 0.3}, "gamut_type": "A"}"#).unwrap();
+		assert!(color.gamut.approx_eq(&Gamut::A));
+		let color: Color = serde_json::from_str(r#"{"xy": {"x": 0.3, "y": 0.3}, "gamut_type": "other"}"#).unwrap();
+		assert_eq!(color.gamut_type, GamutType::Other);
+	}
+
+	#[test]
+	fn component() {
+		assert!(Component::new(0.3, 0.3).is_some());
+		assert!(Component::new(0.1, 0.9).is_none());
+		assert!(Component::new(0.5, 0.1).is_none());
+		assert!(Component::new(-0.1, 0.3).is_none());
+		assert!(Component::new(f32::NAN, 0.3).is_none());
+		for gamut in [Gamut::A, Gamut::B, Gamut::C, RgbSpace::REC_2020.gamut()] {
+			assert!(gamut.red.is_valid() && gamut.green.is_valid() && gamut.blue.is_valid());
+		}
+
+		assert_eq!(Component::try_from((0.4, 0.4)), Ok(Component::unchecked(0.4, 0.4)));
+		assert_eq!(
+			Component::try_from((0.8, 0.8)),
+			Err(InvalidComponent { x: 0.8, y: 0.8 })
+		);
+		assert_eq!(<(f32, f32)>::from(Component::WHITE), (0.3127, 0.329));
+
+		assert_eq!(Component::default(), Component::WHITE);
+		assert_eq!(Gamut::default(), Gamut::C);
+		assert_eq!(RGB8::default(), RGB8::new(0, 0, 0));
+
+		let (a, b) = (Component::unchecked(0.2, 0.4), Component::unchecked(0.4, 0.2));
+		assert_component_eq!((a + b) / 2.0, Component::unchecked(0.3, 0.3), 0.0001);
+		assert_component_eq!(a + (b - a) * 0.25, Component::unchecked(0.25, 0.35), 0.0001);
+	}
+
+	#[test]
+	fn gamut_contains() {
+		let gamut = Gamut::C;
+		assert!(gamut.contains(&Component::unchecked(0.2986f32, 0.3425f32)));
+		assert!(gamut.contains(&Component::unchecked(0.675f32, 0.308f32)));
+		assert!(gamut.contains(&Component::unchecked(0.4f32, 0.4f32)));
+		assert!(!gamut.contains(&Component::unchecked(0.5f32, 0.5f32)));
+		assert!(!gamut.contains(&Component::unchecked(0.01f32, 0.01f32)));
+	}
+
+	#[test]
+	fn gamut_xy_from_rgb_on_edge() {
+		let gamut = Gamut::C;
+
+		assert_component_eq!(
+			Component::unchecked(0.64f32, 0.33f32),
+			gamut.xy_from_rgb8(RGB8::new(255, 0, 0)),
+			0.0001
+		);
+		assert_component_eq!(
+			Component::unchecked(0.3f32, 0.6f32),
+			gamut.xy_from_rgb8(RGB8::new(0, 255, 0)),
+			0.0001
+		);
+		assert_component_eq!(
+			Component::unchecked(0.1535f32, 0.0599f32),
+			gamut.xy_from_rgb8(RGB8::new(0, 0, 255)),
+			0.0001
+		);
+	}
+
+	#[test]
+	fn gamut_xy_from_rgb_saturated() {
+		let white = Component::WHITE;
+		for gamut in [Gamut::A, Gamut::B, Gamut::C] {
+			for space in [RgbSpace::SRGB, RgbSpace::REC_2020] {
+				for rgb in [
+					RGBf32::new(1f32, 0f32, 0f32),
+					RGBf32::new(0f32, 1f32, 0f32),
+					RGBf32::new(0f32, 0f32, 1f32),
+				] {
+					let (xy, brightness) = gamut.xy_from_rgb(rgb, &space);
+					assert_eq!(brightness, 100f32);
+					assert!(xy.distance2(&white) > 0.01, "{:?} in {:?} became white", rgb, gamut);
+					assert_component_eq!(gamut.restrain(&xy), xy, 0.0001);
+				}
+			}
+
+			assert_eq!(
+				gamut.xy_from_rgb(RGBf32::new(0f32, 0f32, 0f32), &RgbSpace::SRGB),
+				(gamut.restrain(&white), 0f32)
+			);
+		}
+	}
+
+	#[test]
+	fn gamut_xy_from_rgb_inside() {
+		let gamut = Gamut::C;
+
+		assert_component_eq!(
+			Component::unchecked(0.3127301, 0.32901987),
+			gamut.xy_from_rgb8(RGB8::new(128, 128, 128)),
+			0.0001
+		);
+	}
+
+	#[test]
+	fn gamut_xy_to_rgb_on_edge() {
+		let gamut = Gamut::C;
+
+		assert_rgb_eq!(
+			RGB8::new(255, 0, 0),
+			gamut.xy_to_rgb8(&Component::unchecked(0.64f32, 0.33f32)),
+			1
+		);
+		assert_rgb_eq!(
+			RGB8::new(0, 236, 0),
+			gamut.xy_to_rgb8(&Component::unchecked(0.3f32, 0.6f32)),
+			1
+		);
+		assert_rgb_eq!(
+			RGB8::new(30, 0, 234),
+			gamut.xy_to_rgb8(&Component::unchecked(0.1535f32, 0.0599f32)),
+			1
+		);
+	}
+
+	#[test]
+	fn gamut_rgb_with_brightness() {
+		let gamut = Gamut::C;
+
+		for rgb in [
+			RGB8::new(128, 128, 128),
+			RGB8::new(200, 100, 50),
+			RGB8::new(20, 10, 5),
+			RGB8::new(255, 0, 0),
+		] {
+			let (xy, brightness) = gamut.xy_from_rgb8_with_brightness(rgb);
+			assert_rgb_eq!(rgb, gamut.xy_to_rgb8_with_brightness(&xy, brightness), 1);
+		}
+
+		let (_, brightness) = gamut.xy_from_rgb8_with_brightness(RGB8::new(255, 0, 0));
+		assert_component_eq!(
+			Component::unchecked(brightness, 0f32),
+			Component::unchecked(100f32, 0f32),
+			0.0001
+		);
+
+		let (xy, brightness) = gamut.xy_from_rgb8_with_brightness(RGB8::new(0, 0, 0));
+		assert_component_eq!(Component::WHITE, xy, 0.0001);
+		assert_rgb_eq!(RGB8::new(0, 0, 0), gamut.xy_to_rgb8_with_brightness(&xy, brightness), 0);
+
+		// dimmed lights no longer report bright colors.
+		assert_rgb_eq!(
+			RGB8::new(26, 26, 26),
+			gamut.xy_to_rgb8_with_brightness(&Component::WHITE, 10f32),
+			1
+		);
+	}
+
+	#[test]
+	fn gamut_xy_to_rgb_inside() {
+		let gamut = Gamut::C;
+
+		// we don't have brightness component, so it is not RGB(128, 128, 128) as in gamut_xy_from_rgb_inside.
+		assert_rgb_eq!(
+			RGB8::new(155, 155, 155),
+			gamut.xy_to_rgb8(&Component::unchecked(0.3127301, 0.32901987)),
+			1
+		);
+	}
+}
@@ -0,0 +1,213 @@
+//! Palettes, for fades, scenes and gradients.
+
+use std::str::FromStr;
+
+use super::{Component, Gamut, Lab, OKLab, RGB8, XYZ};
+
+/// Color space in which palettes are interpolated.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum Interpolation {
+	/// CIE L\*a\*b\*.
+	Lab,
+	/// OKLab, which keeps hues closer to what is expected, particularly for blues.
+	#[default]
+	OKLab,
+}
+
+/// Colors evenly spaced along a line, interpolated in a perceptual color space so transitions between them look even.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+	colors: Vec<XYZ>,
+	interpolation: Interpolation,
+}
+
+impl Palette {
+	/// Palette of `colors`, interpolated in [`Interpolation::OKLab`]. Returns `None` if there are no `colors`.
+	pub fn new(colors: Vec<XYZ>) -> Option<Palette> {
+		if colors.is_empty() {
+			None
+		} else {
+			Some(Palette {
+				colors,
+				interpolation: Interpolation::default(),
+			})
+		}
+	}
+
+	pub fn from_rgb8(colors: &[RGB8]) -> Option<Palette> {
+		Self::new(colors.iter().cloned().map(XYZ::from).collect())
+	}
+
+	/// Palette of `#rrggbb` colors. Panics if any isn't valid, so it is only meant for literals.
+	fn from_hex(colors: &[&str]) -> Palette {
+		let colors: Vec<RGB8> = colors.iter().map(|hex| RGB8::from_str(hex).unwrap()).collect();
+		Self::from_rgb8(&colors).unwrap()
+	}
+
+	pub fn with_interpolation(mut self, interpolation: Interpolation) -> Palette {
+		self.interpolation = interpolation;
+		self
+	}
+
+	pub fn colors(&self) -> &[XYZ] {
+		&self.colors
+	}
+
+	pub fn interpolation(&self) -> Interpolation {
+		self.interpolation
+	}
+
+	/// Color at `position`, between 0 (the first color) and 1 (the last color).
+	pub fn at(&self, position: f32) -> XYZ {
+		let position = position.clamp(0f32, 1f32) * (self.colors.len() - 1) as f32;
+		let index = (position.floor() as usize).min(self.colors.len() - 1);
+		let t = position - index as f32;
+
+		match self.colors.get(index + 1) {
+			Some(next) if t > 0f32 => self.interpolate(&self.colors[index], next, t),
+			_ => self.colors[index],
+		}
+	}
+
+	fn interpolate(&self, a: &XYZ, b: &XYZ, t: f32) -> XYZ {
+		let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+		match self.interpolation {
+			Interpolation::Lab => {
+				let (a, b) = (Lab::from(*a), Lab::from(*b));
+				XYZ::from(Lab::new(lerp(a.l, b.l), lerp(a.a, b.a), lerp(a.b, b.b)))
+			},
+			Interpolation::OKLab => {
+				let (a, b) = (OKLab::from(*a), OKLab::from(*b));
+				XYZ::from(OKLab::new(lerp(a.l, b.l), lerp(a.a, b.a), lerp(a.b, b.b)))
+			},
+		}
+	}
+
+	/// `count` colors evenly spaced along the palette, including its first and last colors.
+	pub fn sample(&self, count: usize) -> Vec<XYZ> {
+		match count {
+			0 => Vec::new(),
+			1 => vec![self.at(0f32)],
+			_ => (0..count).map(|i| self.at(i as f32 / (count - 1) as f32)).collect(),
+		}
+	}
+
+	/// Like [`Palette::sample`], as colors restrained to `gamut`, like for
+	/// [`Light::set_gradient`](crate::Light::set_gradient).
+	pub fn sample_xy(&self, count: usize, gamut: &Gamut) -> Vec<Component> {
+		self.sample(count)
+			.iter()
+			.map(|xyz| gamut.restrain(&xyz.xy().unwrap_or(Component::WHITE)))
+			.collect()
+	}
+
+	/// Reds, oranges and yellows.
+	pub fn sunset() -> Palette {
+		Self::from_hex(&["#ff2a00", "#ff7a00", "#ffc400"])
+	}
+
+	/// Flames, from deep red to bright yellow.
+	pub fn fire() -> Palette {
+		Self::from_hex(&["#b00000", "#ff3c00", "#ff9a00", "#ffe45c"])
+	}
+
+	/// Deep blues to turquoise.
+	pub fn ocean() -> Palette {
+		Self::from_hex(&["#0018a8", "#0077ff", "#00d4c8"])
+	}
+
+	/// Greens to yellowish greens.
+	pub fn forest() -> Palette {
+		Self::from_hex(&["#0b6e0b", "#4caf1a", "#b8e000"])
+	}
+
+	/// Greens, cyans and purples of the northern lights.
+	pub fn aurora() -> Palette {
+		Self::from_hex(&["#00ff6a", "#00e0ff", "#7a3cff", "#d400ff"])
+	}
+
+	/// Every hue, from red to violet.
+	pub fn rainbow() -> Palette {
+		Self::from_hex(&["#ff0000", "#ff8000", "#ffff00", "#00ff00", "#0000ff", "#8000ff"])
+	}
+
+	/// Warm to cool whites.
+	pub fn whites() -> Palette {
+		Self::new(vec![
+			XYZ::from_xy(&Component::from_kelvin(2200f32), 1f32),
+			XYZ::from_xy(&Component::from_kelvin(6500f32), 1f32),
+		])
+		.unwrap()
+	}
+
+	/// Every built-in palette, by name.
+	pub fn builtins() -> Vec<(&'static str, Palette)> {
+		vec![
+			("sunset", Self::sunset()),
+			("fire", Self::fire()),
+			("ocean", Self::ocean()),
+			("forest", Self::forest()),
+			("aurora", Self::aurora()),
+			("rainbow", Self::rainbow()),
+			("whites", Self::whites()),
+		]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert_component_eq;
+
+	#[test]
+	fn palette_sample() {
+		let palette = Palette::from_rgb8(&[RGB8::new(255, 0, 0), RGB8::new(0, 0, 255)]).unwrap();
+		assert!(Palette::new(Vec::new()).is_none());
+
+		let samples = palette.sample(5);
+		assert_eq!(samples.len(), 5);
+		assert_eq!(samples[0], palette.colors()[0]);
+		assert_eq!(samples[4], palette.colors()[1]);
+		assert!(palette.sample(0).is_empty());
+
+		// lightness changes evenly.
+		let lightness: Vec<f32> = samples.iter().map(|xyz| OKLab::from(*xyz).l).collect();
+		let steps: Vec<f32> = lightness.windows(2).map(|pair| pair[1] - pair[0]).collect();
+		assert!(steps.iter().all(|step| (step - steps[0]).abs() < 0.001));
+
+		let lab = palette.clone().with_interpolation(Interpolation::Lab);
+		let lightness: Vec<f32> = lab.sample(5).iter().map(|xyz| Lab::from(*xyz).l).collect();
+		assert!((lightness[2] - (lightness[0] + lightness[4]) / 2f32).abs() < 0.001);
+	}
+
+	#[test]
+	fn palette_sample_xy() {
+		for (_, palette) in Palette::builtins() {
+			for xy in palette.sample_xy(7, &Gamut::A) {
+				assert_component_eq!(Gamut::A.restrain(&xy), xy, 0.0001);
+			}
+		}
+
+		let xy = Palette::from_rgb8(&[RGB8::new(0, 0, 255)])
+			.unwrap()
+			.sample_xy(1, &Gamut::C);
+		assert_component_eq!(Gamut::C.xy_from_rgb8(RGB8::new(0, 0, 255)), xy[0], 0.0001);
+	}
+
+	#[test]
+	fn palette_hue() {
+		// blue to white keeps its hue in OKLab, but drifts towards purple in Lab.
+		let palette = Palette::from_rgb8(&[RGB8::new(0, 0, 255), RGB8::new(255, 255, 255)]).unwrap();
+		let hue = |xyz: XYZ| {
+			let lab = OKLab::from(xyz);
+			lab.b.atan2(lab.a).to_degrees()
+		};
+
+		let blue = hue(palette.colors()[0]);
+		let oklab = hue(palette.at(0.5));
+		let lab = hue(palette.clone().with_interpolation(Interpolation::Lab).at(0.5));
+		assert!((oklab - blue).abs() < 0.1);
+		assert!((lab - blue).abs() > 1.0);
+	}
+}
@@ -0,0 +1,418 @@
+//! RGB color spaces, and RGB colors with more precision than [`RGB8`].
+//!
+//! Conversions are computed in `f64` from the primaries and white point of each space, and adapted to the D65 white
+//! point of [`XYZ`] when needed.
+
+use super::{Component, Gamut, RGB8, XYZ};
+
+type Matrix = [[f64; 3]; 3];
+
+/// Bradford cone response matrix, for chromatic adaptation.
+const BRADFORD: Matrix = [
+	[0.8951, 0.2664, -0.1614],
+	[-0.7502, 1.7135, 0.0367],
+	[0.0389, -0.0685, 1.0296],
+];
+
+/// RGB with 16 bits per channel. Defaults to black.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct RGB16 {
+	pub r: u16,
+	pub g: u16,
+	pub b: u16,
+}
+
+/// RGB with floating point channels, nominally between 0 and 1. Channels out of that range are out of the gamut of
+/// the color space. Defaults to black.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RGBf32 {
+	pub r: f32,
+	pub g: f32,
+	pub b: f32,
+}
+
+/// Transfer function between linear light and encoded RGB channels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Transfer {
+	Srgb,
+	Rec2020,
+	Gamma(f64),
+}
+
+/// An RGB color space, defined by its primaries, white point and transfer function.
+#[derive(Debug, Clone)]
+pub struct RgbSpace {
+	red: Component,
+	green: Component,
+	blue: Component,
+	white: Component,
+	transfer: Transfer,
+	/// From linear RGB to XYZ relative to D65.
+	to_xyz: Matrix,
+	/// From XYZ relative to D65 to linear RGB.
+	from_xyz: Matrix,
+}
+
+// matrix operations are `const`, so the matrices of the built-in spaces are computed at compile time.
+
+const fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+	let mut m = [[0f64; 3]; 3];
+	let mut i = 0;
+	while i < 3 {
+		let mut j = 0;
+		while j < 3 {
+			m[i][j] = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+			j += 1;
+		}
+		i += 1;
+	}
+	m
+}
+
+const fn apply(m: &Matrix, v: [f64; 3]) -> [f64; 3] {
+	[
+		m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+		m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+		m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+	]
+}
+
+const fn cofactor(m: &Matrix, i: usize, j: usize) -> f64 {
+	let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+	let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+	m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+}
+
+const fn invert(m: &Matrix) -> Matrix {
+	let determinant = m[0][0] * cofactor(m, 0, 0) + m[0][1] * cofactor(m, 0, 1) + m[0][2] * cofactor(m, 0, 2);
+
+	let mut inverse = [[0f64; 3]; 3];
+	let mut i = 0;
+	while i < 3 {
+		let mut j = 0;
+		while j < 3 {
+			inverse[i][j] = cofactor(m, j, i) / determinant;
+			j += 1;
+		}
+		i += 1;
+	}
+	inverse
+}
+
+const fn diagonal(v: [f64; 3]) -> Matrix {
+	[[v[0], 0.0, 0.0], [0.0, v[1], 0.0], [0.0, 0.0, v[2]]]
+}
+
+/// XYZ, with a luminance of 1, of chromaticity `xy`.
+const fn xyz_of(xy: &Component) -> [f64; 3] {
+	let (x, y) = (xy.x as f64, xy.y as f64);
+	[x / y, 1.0, (1.0 - x - y) / y]
+}
+
+impl Transfer {
+	const REC2020_ALPHA: f64 = 1.09929682680944;
+	const REC2020_BETA: f64 = 0.018053968510807;
+
+	fn decode(&self, c: f64) -> f64 {
+		let (sign, c) = (c.signum(), c.abs());
+		let linear = match self {
+			Transfer::Srgb if c > 0.04045 => ((c + 0.055) / 1.055).powf(2.4),
+			Transfer::Srgb => c / 12.92,
+			Transfer::Rec2020 if c >= 4.5 * Self::REC2020_BETA => {
+				((c + Self::REC2020_ALPHA - 1.0) / Self::REC2020_ALPHA).powf(1.0 / 0.45)
+			},
+			Transfer::Rec2020 => c / 4.5,
+			Transfer::Gamma(gamma) => c.powf(*gamma),
+		};
+		sign * linear
+	}
+
+	fn encode(&self, c: f64) -> f64 {
+		let (sign, c) = (c.signum(), c.abs());
+		let encoded = match self {
+			Transfer::Srgb if c > 0.0031308 => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+			Transfer::Srgb => c * 12.92,
+			Transfer::Rec2020 if c >= Self::REC2020_BETA => {
+				Self::REC2020_ALPHA * c.powf(0.45) - (Self::REC2020_ALPHA - 1.0)
+			},
+			Transfer::Rec2020 => c * 4.5,
+			Transfer::Gamma(gamma) => c.powf(1.0 / gamma),
+		};
+		sign * encoded
+	}
+}
+
+impl RgbSpace {
+	/// Adobe RGB (1998).
+	pub const ADOBE_RGB: RgbSpace = RgbSpace::new(
+		Component { x: 0.64, y: 0.33 },
+		Component { x: 0.21, y: 0.71 },
+		Component { x: 0.15, y: 0.06 },
+		Component::WHITE,
+		Transfer::Gamma(563.0 / 256.0),
+	);
+	/// Display P3, the wide gamut of recent Apple devices, with the sRGB transfer function.
+	pub const DISPLAY_P3: RgbSpace = RgbSpace::new(
+		Component { x: 0.68, y: 0.32 },
+		Component { x: 0.265, y: 0.69 },
+		Component { x: 0.15, y: 0.06 },
+		Component::WHITE,
+		Transfer::Srgb,
+	);
+	/// ITU-R BT.2020, of UHD television.
+	pub const REC_2020: RgbSpace = RgbSpace::new(
+		Component { x: 0.708, y: 0.292 },
+		Component { x: 0.17, y: 0.797 },
+		Component { x: 0.131, y: 0.046 },
+		Component::WHITE,
+		Transfer::Rec2020,
+	);
+	/// sRGB, the color space of most screens and images.
+	pub const SRGB: RgbSpace = RgbSpace::new(
+		Component { x: 0.64, y: 0.33 },
+		Component { x: 0.3, y: 0.6 },
+		Component { x: 0.15, y: 0.06 },
+		Component::WHITE,
+		Transfer::Srgb,
+	);
+
+	const fn new(red: Component, green: Component, blue: Component, white: Component, transfer: Transfer) -> RgbSpace {
+		let to_xyz = Self::matrix(&red, &green, &blue, &white);
+
+		RgbSpace {
+			red,
+			green,
+			blue,
+			white,
+			transfer,
+			to_xyz,
+			from_xyz: invert(&to_xyz),
+		}
+	}
+
+	/// Matrix from linear RGB to XYZ relative to D65, adapted through Bradford if `white` isn't D65.
+	const fn matrix(red: &Component, green: &Component, blue: &Component, white: &Component) -> Matrix {
+		let (r, g, b) = (xyz_of(red), xyz_of(green), xyz_of(blue));
+		let primaries = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+		let white = xyz_of(white);
+
+		let matrix = multiply(&primaries, &diagonal(apply(&invert(&primaries), white)));
+
+		let d65 = xyz_of(&Component::WHITE);
+		if white[0] == d65[0] && white[1] == d65[1] && white[2] == d65[2] {
+			return matrix;
+		}
+
+		let (source, destination) = (apply(&BRADFORD, white), apply(&BRADFORD, d65));
+		let cone = diagonal([
+			destination[0] / source[0],
+			destination[1] / source[1],
+			destination[2] / source[2],
+		]);
+		let adaptation = multiply(&invert(&BRADFORD), &multiply(&cone, &BRADFORD));
+		multiply(&adaptation, &matrix)
+	}
+
+	/// Colors reachable in the space.
+	pub fn gamut(&self) -> Gamut {
+		Gamut::new(self.red, self.green, self.blue)
+	}
+
+	pub fn red(&self) -> Component {
+		self.red
+	}
+
+	pub fn green(&self) -> Component {
+		self.green
+	}
+
+	pub fn blue(&self) -> Component {
+		self.blue
+	}
+
+	pub fn white(&self) -> Component {
+		self.white
+	}
+
+	/// Converts linear RGB channels to XYZ.
+	pub(super) fn linear_to_xyz(&self, linear: [f64; 3]) -> XYZ {
+		let [x, y, z] = apply(&self.to_xyz, linear);
+		XYZ::new(x as f32, y as f32, z as f32)
+	}
+
+	/// Converts XYZ to linear RGB channels, which may be out of range.
+	pub(super) fn xyz_to_linear(&self, xyz: &XYZ) -> [f64; 3] {
+		apply(&self.from_xyz, [xyz.x as f64, xyz.y as f64, xyz.z as f64])
+	}
+
+	pub(super) fn decode(&self, c: f64) -> f64 {
+		self.transfer.decode(c)
+	}
+
+	pub(super) fn encode(&self, c: f64) -> f64 {
+		self.transfer.encode(c)
+	}
+
+	pub fn to_xyz(&self, rgb: RGBf32) -> XYZ {
+		self.linear_to_xyz([rgb.r, rgb.g, rgb.b].map(|c| self.decode(c as f64)))
+	}
+
+	/// Converts `xyz` to the space, without clamping channels out of its gamut.
+	pub fn from_xyz(&self, xyz: &XYZ) -> RGBf32 {
+		let [r, g, b] = self.xyz_to_linear(xyz).map(|c| self.encode(c) as f32);
+		RGBf32 { r, g, b }
+	}
+}
+
+impl RGB16 {
+	pub fn new(r: u16, g: u16, b: u16) -> RGB16 {
+		RGB16 { r, g, b }
+	}
+}
+
+impl RGBf32 {
+	pub fn new(r: f32, g: f32, b: f32) -> RGBf32 {
+		RGBf32 { r, g, b }
+	}
+
+	/// Whether every channel is between 0 and 1.
+	pub fn in_gamut(&self) -> bool {
+		[self.r, self.g, self.b].iter().all(|c| (0f32..=1f32).contains(c))
+	}
+}
+
+impl From<RGB8> for RGBf32 {
+	fn from(rgb: RGB8) -> Self {
+		RGBf32::new(rgb.r as f32 / 255f32, rgb.g as f32 / 255f32, rgb.b as f32 / 255f32)
+	}
+}
+
+impl From<RGB16> for RGBf32 {
+	fn from(rgb: RGB16) -> Self {
+		let channel = |c: u16| c as f32 / u16::MAX as f32;
+		RGBf32::new(channel(rgb.r), channel(rgb.g), channel(rgb.b))
+	}
+}
+
+/// Channels are clamped.
+impl From<RGBf32> for RGB8 {
+	fn from(rgb: RGBf32) -> Self {
+		let channel = |c: f32| (c.clamp(0f32, 1f32) * 255f32).round() as u8;
+		RGB8::new(channel(rgb.r), channel(rgb.g), channel(rgb.b))
+	}
+}
+
+/// Channels are clamped.
+impl From<RGBf32> for RGB16 {
+	fn from(rgb: RGBf32) -> Self {
+		let channel = |c: f32| (c.clamp(0f32, 1f32) * u16::MAX as f32).round() as u16;
+		RGB16::new(channel(rgb.r), channel(rgb.g), channel(rgb.b))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert_component_eq;
+
+	const SPACES: [RgbSpace; 4] = [
+		RgbSpace::SRGB,
+		RgbSpace::DISPLAY_P3,
+		RgbSpace::REC_2020,
+		RgbSpace::ADOBE_RGB,
+	];
+
+	/// Deterministic pseudo random numbers between 0 and 1, so failures can be reproduced.
+	fn random(count: usize) -> impl Iterator<Item = f32> {
+		let mut state = 0x2545f4914f6cdd1du64;
+		(0..count).map(move |_| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			(state >> 40) as f32 / (1u64 << 24) as f32
+		})
+	}
+
+	#[test]
+	fn srgb_matrix() {
+		let xyz = RgbSpace::SRGB.to_xyz(RGBf32::new(1.0, 0.0, 0.0));
+		assert!((xyz.x - 0.4124).abs() < 0.0001 && (xyz.y - 0.2126).abs() < 0.0001 && (xyz.z - 0.0193).abs() < 0.0001);
+
+		let white = RgbSpace::SRGB.to_xyz(RGBf32::new(1.0, 1.0, 1.0));
+		assert_component_eq!(Component::WHITE, white.xy().unwrap(), 0.00001);
+		assert!((white.y - 1.0).abs() < 0.00001);
+	}
+
+	/// Any color of each space goes through XYZ and back within 1e-5 per channel, in linear light as pure gamma curves
+	/// amplify the rounding of XYZ to `f32` near black.
+	#[test]
+	fn rgb_round_trip() {
+		let values: Vec<f32> = random(3 * 1000).collect();
+
+		for space in SPACES.iter() {
+			let linear = |rgb: RGBf32| [rgb.r, rgb.g, rgb.b].map(|c| space.decode(c as f64));
+			for rgb in values.chunks_exact(3).map(|c| RGBf32::new(c[0], c[1], c[2])) {
+				let back = space.from_xyz(&space.to_xyz(rgb));
+				assert!(
+					linear(rgb).iter().zip(linear(back)).all(|(a, b)| (a - b).abs() < 1e-5),
+					"{:?} came back as {:?} in {:?}",
+					rgb,
+					back,
+					space
+				);
+			}
+		}
+	}
+
+	/// Any color inside the gamut of each space goes to RGB and back within 1e-4 in xy, and 0.01% in brightness.
+	#[test]
+	fn xy_round_trip() {
+		let values: Vec<f32> = random(3 * 1000).collect();
+
+		for space in SPACES.iter() {
+			let gamut = space.gamut();
+			for weights in values.chunks_exact(3) {
+				let sum: f32 = weights.iter().sum();
+				let (wr, wg, wb) = (weights[0] / sum, weights[1] / sum, weights[2] / sum);
+				let xy = Component::unchecked(
+					wr * gamut.red.x + wg * gamut.green.x + wb * gamut.blue.x,
+					wr * gamut.red.y + wg * gamut.green.y + wb * gamut.blue.y,
+				);
+				let brightness = 1f32 + weights[0] * 99f32;
+
+				let rgb = gamut.xy_to_rgb(&xy, brightness, space);
+				assert!(rgb.in_gamut(), "{:?} out of gamut in {:?}", rgb, space);
+
+				let (back, back_brightness) = gamut.xy_from_rgb(rgb, space);
+				assert_component_eq!(xy, back, 0.0001);
+				assert!((brightness - back_brightness).abs() < 0.01);
+			}
+		}
+	}
+
+	#[test]
+	fn rgb16() {
+		let rgb = RGBf32::from(RGB16::new(u16::MAX, 0, 32768));
+		assert!((rgb.b - 0.5).abs() < 0.0001);
+		assert_eq!(RGB16::from(rgb), RGB16::new(u16::MAX, 0, 32768));
+
+		let rgb = RGB8::from(RGBf32::new(1.5, -0.2, 0.5));
+		assert_eq!((rgb.r, rgb.g, rgb.b), (255, 0, 128));
+	}
+
+	#[test]
+	fn gamut_xy_from_rgb_outside_horseshoe() {
+		// red just beyond the end of the horseshoe, where rounding can put the primaries of wide spaces.
+		let space = RgbSpace::new(
+			Component::unchecked(0.74, 0.26),
+			RgbSpace::REC_2020.green,
+			RgbSpace::REC_2020.blue,
+			Component::WHITE,
+			Transfer::Srgb,
+		);
+
+		let (xy, brightness) = Gamut::C.xy_from_rgb(RGBf32::new(1f32, 0f32, 0f32), &space);
+		assert_eq!(brightness, 100f32);
+		assert_component_eq!(Gamut::C.red, xy, 0.01);
+	}
+}
@@ -0,0 +1,434 @@
+//! Color spaces beyond CIE xy and sRGB. They are all converted to each other through [`XYZ`], relative to the D65 white
+//! point, without loss besides rounding to [`RGB8`].
+//!
+//! sRGB conversions go through [`RgbSpace::SRGB`].
+
+use super::{Component, RGBf32, RgbSpace, RGB8};
+
+/// D65 white point in CIE XYZ, as derived from [`Component::WHITE`].
+const WHITE: XYZ = XYZ {
+	x: 0.9504559,
+	y: 1.0,
+	z: 1.0890578,
+};
+
+/// CIE 1931 XYZ, where `y` is the luminance, 1 being the one of white.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XYZ {
+	pub x: f32,
+	pub y: f32,
+	pub z: f32,
+}
+
+/// sRGB hue (in degrees), saturation and value, the latter two between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HSV {
+	pub h: f32,
+	pub s: f32,
+	pub v: f32,
+}
+
+/// sRGB hue (in degrees), saturation and lightness, the latter two between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HSL {
+	pub h: f32,
+	pub s: f32,
+	pub l: f32,
+}
+
+/// CIE L\*a\*b\*, with lightness between 0 and 100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+	pub l: f32,
+	pub a: f32,
+	pub b: f32,
+}
+
+/// [OKLab](https://bottosson.github.io/posts/oklab/), a perceptual color space better at predicting hue than
+/// [`Lab`], with lightness between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OKLab {
+	pub l: f32,
+	pub a: f32,
+	pub b: f32,
+}
+
+/// CIE L\*C\*h, the cylindrical form of [`Lab`], with hue in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LCh {
+	pub l: f32,
+	pub c: f32,
+	pub h: f32,
+}
+
+fn xyz_from_rgb(rgb: [f32; 3]) -> XYZ {
+	RgbSpace::SRGB.to_xyz(RGBf32::new(rgb[0], rgb[1], rgb[2]))
+}
+
+fn xyz_to_rgb(xyz: &XYZ) -> [f32; 3] {
+	let rgb = RgbSpace::SRGB.from_xyz(xyz);
+	[rgb.r, rgb.g, rgb.b]
+}
+
+fn rgb8_from_rgb(rgb: [f32; 3]) -> RGB8 {
+	RGB8::from(RGBf32::new(rgb[0], rgb[1], rgb[2]))
+}
+
+fn rgb8_to_rgb(rgb: &RGB8) -> [f32; 3] {
+	let rgb = RGBf32::from(*rgb);
+	[rgb.r, rgb.g, rgb.b]
+}
+
+/// Hue, in degrees, of sRGB channels, along with their maximum and minimum.
+fn hue(rgb: [f32; 3]) -> (f32, f32, f32) {
+	let [r, g, b] = rgb;
+	let max = r.max(g).max(b);
+	let min = r.min(g).min(b);
+	let delta = max - min;
+
+	let h = if delta <= 0f32 {
+		0f32
+	} else if max == r {
+		60f32 * ((g - b) / delta).rem_euclid(6f32)
+	} else if max == g {
+		60f32 * ((b - r) / delta + 2f32)
+	} else {
+		60f32 * ((r - g) / delta + 4f32)
+	};
+
+	(h, max, min)
+}
+
+/// sRGB channels of a hue, chroma and the amount to add to each channel.
+fn rgb_from_hue(h: f32, c: f32, m: f32) -> [f32; 3] {
+	let h = h.rem_euclid(360f32) / 60f32;
+	let x = c * (1f32 - (h.rem_euclid(2f32) - 1f32).abs());
+
+	let (r, g, b) = match h as u32 {
+		0 => (c, x, 0f32),
+		1 => (x, c, 0f32),
+		2 => (0f32, c, x),
+		3 => (0f32, x, c),
+		4 => (x, 0f32, c),
+		_ => (c, 0f32, x),
+	};
+
+	[r + m, g + m, b + m]
+}
+
+fn lab_f(t: f32) -> f32 {
+	const EPSILON: f32 = 216.0 / 24389.0;
+	const KAPPA: f32 = 24389.0 / 27.0;
+
+	if t > EPSILON {
+		t.cbrt()
+	} else {
+		(KAPPA * t + 16.0) / 116.0
+	}
+}
+
+fn lab_f_inverse(t: f32) -> f32 {
+	const DELTA: f32 = 6.0 / 29.0;
+
+	if t > DELTA {
+		t.powi(3)
+	} else {
+		3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+	}
+}
+
+impl XYZ {
+	pub fn new(x: f32, y: f32, z: f32) -> XYZ {
+		XYZ { x, y, z }
+	}
+
+	/// Color with chromaticity `xy` and `luminance` (the `y` of XYZ).
+	pub fn from_xy(xy: &Component, luminance: f32) -> XYZ {
+		if xy.y <= 0f32 {
+			return XYZ::new(0f32, 0f32, 0f32);
+		}
+
+		XYZ {
+			x: xy.x * luminance / xy.y,
+			y: luminance,
+			z: (1f32 - xy.x - xy.y) * luminance / xy.y,
+		}
+	}
+
+	/// Chromaticity of the color, if it isn't black and is inside the CIE horseshoe.
+	pub fn xy(&self) -> Option<Component> {
+		let sum = self.x + self.y + self.z;
+		if sum <= 0f32 {
+			return None;
+		}
+
+		Component::new(self.x / sum, self.y / sum)
+	}
+}
+
+impl HSV {
+	pub fn new(h: f32, s: f32, v: f32) -> HSV {
+		HSV { h, s, v }
+	}
+
+	fn from_rgb(rgb: [f32; 3]) -> HSV {
+		let (h, max, min) = hue(rgb);
+		let s = if max <= 0f32 { 0f32 } else { (max - min) / max };
+
+		HSV { h, s, v: max }
+	}
+
+	fn to_rgb(self) -> [f32; 3] {
+		let c = self.v * self.s;
+		rgb_from_hue(self.h, c, self.v - c)
+	}
+}
+
+impl HSL {
+	pub fn new(h: f32, s: f32, l: f32) -> HSL {
+		HSL { h, s, l }
+	}
+
+	fn from_rgb(rgb: [f32; 3]) -> HSL {
+		let (h, max, min) = hue(rgb);
+		let l = (max + min) / 2f32;
+		let s = if max - min <= 0f32 {
+			0f32
+		} else {
+			(max - min) / (1f32 - (2f32 * l - 1f32).abs())
+		};
+
+		HSL { h, s, l }
+	}
+
+	fn to_rgb(self) -> [f32; 3] {
+		let c = (1f32 - (2f32 * self.l - 1f32).abs()) * self.s;
+		rgb_from_hue(self.h, c, self.l - c / 2f32)
+	}
+}
+
+impl Lab {
+	pub fn new(l: f32, a: f32, b: f32) -> Lab {
+		Lab { l, a, b }
+	}
+}
+
+impl OKLab {
+	pub fn new(l: f32, a: f32, b: f32) -> OKLab {
+		OKLab { l, a, b }
+	}
+}
+
+impl LCh {
+	pub fn new(l: f32, c: f32, h: f32) -> LCh {
+		LCh { l, c, h }
+	}
+}
+
+impl From<RGB8> for XYZ {
+	fn from(rgb: RGB8) -> Self {
+		xyz_from_rgb(rgb8_to_rgb(&rgb))
+	}
+}
+
+/// Channels out of the sRGB gamut are clamped.
+impl From<XYZ> for RGB8 {
+	fn from(xyz: XYZ) -> Self {
+		rgb8_from_rgb(xyz_to_rgb(&xyz))
+	}
+}
+
+impl From<RGB8> for HSV {
+	fn from(rgb: RGB8) -> Self {
+		HSV::from_rgb(rgb8_to_rgb(&rgb))
+	}
+}
+
+impl From<HSV> for RGB8 {
+	fn from(hsv: HSV) -> Self {
+		rgb8_from_rgb(hsv.to_rgb())
+	}
+}
+
+impl From<XYZ> for HSV {
+	fn from(xyz: XYZ) -> Self {
+		HSV::from_rgb(xyz_to_rgb(&xyz))
+	}
+}
+
+impl From<HSV> for XYZ {
+	fn from(hsv: HSV) -> Self {
+		xyz_from_rgb(hsv.to_rgb())
+	}
+}
+
+impl From<RGB8> for HSL {
+	fn from(rgb: RGB8) -> Self {
+		HSL::from_rgb(rgb8_to_rgb(&rgb))
+	}
+}
+
+impl From<HSL> for RGB8 {
+	fn from(hsl: HSL) -> Self {
+		rgb8_from_rgb(hsl.to_rgb())
+	}
+}
+
+impl From<XYZ> for HSL {
+	fn from(xyz: XYZ) -> Self {
+		HSL::from_rgb(xyz_to_rgb(&xyz))
+	}
+}
+
+impl From<HSL> for XYZ {
+	fn from(hsl: HSL) -> Self {
+		xyz_from_rgb(hsl.to_rgb())
+	}
+}
+
+impl From<XYZ> for Lab {
+	fn from(xyz: XYZ) -> Self {
+		let fx = lab_f(xyz.x / WHITE.x);
+		let fy = lab_f(xyz.y / WHITE.y);
+		let fz = lab_f(xyz.z / WHITE.z);
+
+		Lab {
+			l: 116.0 * fy - 16.0,
+			a: 500.0 * (fx - fy),
+			b: 200.0 * (fy - fz),
+		}
+	}
+}
+
+impl From<Lab> for XYZ {
+	fn from(lab: Lab) -> Self {
+		let fy = (lab.l + 16.0) / 116.0;
+		let fx = fy + lab.a / 500.0;
+		let fz = fy - lab.b / 200.0;
+
+		XYZ {
+			x: WHITE.x * lab_f_inverse(fx),
+			y: WHITE.y * lab_f_inverse(fy),
+			z: WHITE.z * lab_f_inverse(fz),
+		}
+	}
+}
+
+impl From<Lab> for LCh {
+	fn from(lab: Lab) -> Self {
+		LCh {
+			l: lab.l,
+			c: lab.a.hypot(lab.b),
+			h: lab.b.atan2(lab.a).to_degrees().rem_euclid(360f32),
+		}
+	}
+}
+
+impl From<LCh> for Lab {
+	fn from(lch: LCh) -> Self {
+		let h = lch.h.to_radians();
+
+		Lab {
+			l: lch.l,
+			a: lch.c * h.cos(),
+			b: lch.c * h.sin(),
+		}
+	}
+}
+
+impl From<XYZ> for LCh {
+	fn from(xyz: XYZ) -> Self {
+		LCh::from(Lab::from(xyz))
+	}
+}
+
+impl From<LCh> for XYZ {
+	fn from(lch: LCh) -> Self {
+		XYZ::from(Lab::from(lch))
+	}
+}
+
+impl From<XYZ> for OKLab {
+	fn from(xyz: XYZ) -> Self {
+		let l = (0.818933 * xyz.x + 0.36186674 * xyz.y - 0.12885971 * xyz.z).cbrt();
+		let m = (0.03298454 * xyz.x + 0.9293119 * xyz.y + 0.03614564 * xyz.z).cbrt();
+		let s = (0.0482003 * xyz.x + 0.26436627 * xyz.y + 0.6338517 * xyz.z).cbrt();
+
+		OKLab {
+			l: 0.21045426 * l + 0.7936178 * m - 0.00407205 * s,
+			a: 1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+			b: 0.02590404 * l + 0.78277177 * m - 0.80867577 * s,
+		}
+	}
+}
+
+impl From<OKLab> for XYZ {
+	fn from(lab: OKLab) -> Self {
+		let l = (lab.l + 0.39633778 * lab.a + 0.21580376 * lab.b).powi(3);
+		let m = (lab.l - 0.10556135 * lab.a - 0.06385417 * lab.b).powi(3);
+		let s = (lab.l - 0.08948418 * lab.a - 1.2914855 * lab.b).powi(3);
+
+		XYZ {
+			x: 1.2270138 * l - 0.5578 * m + 0.28125615 * s,
+			y: -0.04058018 * l + 1.1122569 * m - 0.07167668 * s,
+			z: -0.07638128 * l - 0.42148198 * m + 1.5861632 * s,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert_rgb_eq;
+
+	fn assert_xyz_eq(a: XYZ, b: XYZ) {
+		assert!(
+			(a.x - b.x).abs() < 0.0001 && (a.y - b.y).abs() < 0.0001 && (a.z - b.z).abs() < 0.0001,
+			"{:?} != {:?}",
+			a,
+			b
+		);
+	}
+
+	#[test]
+	fn hsv() {
+		assert_eq!(HSV::from(RGB8::new(255, 0, 0)), HSV::new(0.0, 1.0, 1.0));
+		assert_eq!(HSV::from(RGB8::new(0, 0, 255)), HSV::new(240.0, 1.0, 1.0));
+		assert_rgb_eq!(RGB8::new(0, 128, 0), RGB8::from(HSV::new(120.0, 1.0, 0.5)), 0);
+		assert_rgb_eq!(RGB8::new(255, 0, 255), RGB8::from(HSV::new(-60.0, 1.0, 1.0)), 0);
+	}
+
+	#[test]
+	fn hsl() {
+		assert_eq!(HSL::from(RGB8::new(255, 255, 255)), HSL::new(0.0, 0.0, 1.0));
+		assert_rgb_eq!(RGB8::new(255, 255, 0), RGB8::from(HSL::new(60.0, 1.0, 0.5)), 0);
+		assert_rgb_eq!(RGB8::new(32, 64, 96), RGB8::from(HSL::new(210.0, 0.5, 0.25)), 0);
+	}
+
+	#[test]
+	fn lab() {
+		let white = Lab::from(XYZ::from(RGB8::new(255, 255, 255)));
+		assert!((white.l - 100.0).abs() < 0.01 && white.a.abs() < 0.01 && white.b.abs() < 0.01);
+
+		let red = Lab::from(XYZ::from(RGB8::new(255, 0, 0)));
+		assert!((red.l - 53.24).abs() < 0.01 && (red.a - 80.09).abs() < 0.01 && (red.b - 67.20).abs() < 0.01);
+
+		let white = OKLab::from(XYZ::from(RGB8::new(255, 255, 255)));
+		assert!((white.l - 1.0).abs() < 0.001 && white.a.abs() < 0.001 && white.b.abs() < 0.001);
+
+		let lch = LCh::from(red);
+		assert!((lch.c - 104.55).abs() < 0.01 && (lch.h - 40.0).abs() < 0.01);
+	}
+
+	#[test]
+	fn round_trip() {
+		let xyz = XYZ::new(0.2, 0.3, 0.15);
+
+		assert_xyz_eq(xyz, XYZ::from(HSV::from(xyz)));
+		assert_xyz_eq(xyz, XYZ::from(HSL::from(xyz)));
+		assert_xyz_eq(xyz, XYZ::from(Lab::from(xyz)));
+		assert_xyz_eq(xyz, XYZ::from(LCh::from(xyz)));
+		assert_xyz_eq(xyz, XYZ::from(OKLab::from(xyz)));
+		assert_xyz_eq(xyz, XYZ::from_xy(&xyz.xy().unwrap(), xyz.y));
+	}
+}
@@ -0,0 +1,150 @@
+//! Conversions between color temperatures and CIE xy, along the
+//! [Planckian locus](https://en.wikipedia.org/wiki/Planckian_locus).
+
+use super::{Component, MirekSchema, Temperature};
+
+/// Lowest color temperature, in Kelvin, the conversions are accurate for.
+pub const KELVIN_MIN: f32 = 1667.0;
+/// Highest color temperature, in Kelvin, the conversions are accurate for.
+pub const KELVIN_MAX: f32 = 25000.0;
+
+/// Converts between Kelvin and mirek, which are each other's inverse, scaled by a million.
+pub fn mirek_from_kelvin(kelvin: f32) -> u32 {
+	(1_000_000.0 / kelvin).round() as u32
+}
+
+/// See [`mirek_from_kelvin`].
+pub fn kelvin_from_mirek(mirek: u32) -> f32 {
+	1_000_000.0 / mirek as f32
+}
+
+impl Component {
+	/// Color of a black body at `kelvin`, clamped between [`KELVIN_MIN`] and [`KELVIN_MAX`].
+	///
+	/// Uses the cubic spline approximation of Kim et al.
+	pub fn from_kelvin(kelvin: f32) -> Component {
+		let t = kelvin.clamp(KELVIN_MIN, KELVIN_MAX);
+		let (t2, t3) = (t * t, t * t * t);
+
+		let x = if t <= 4000.0 {
+			-0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+		} else {
+			-3.0258469e9 / t3 + 2.107038e6 / t2 + 0.2226347e3 / t + 0.240390
+		};
+		let (x2, x3) = (x * x, x * x * x);
+
+		let y = if t <= 2222.0 {
+			-1.1063814 * x3 - 1.3481102 * x2 + 2.1855583 * x - 0.20219683
+		} else if t <= 4000.0 {
+			-0.9549476 * x3 - 1.3741859 * x2 + 2.09137 * x - 0.16748867
+		} else {
+			3.081758 * x3 - 5.873387 * x2 + 3.7511299 * x - 0.37001483
+		};
+
+		Component { x, y }
+	}
+
+	pub fn from_mirek(mirek: u32) -> Component {
+		Self::from_kelvin(kelvin_from_mirek(mirek))
+	}
+
+	/// Correlated color temperature, in Kelvin, of the color: the one of the closest black body.
+	///
+	/// Uses McCamy's approximation, which is accurate for colors close to the Planckian locus (within 1.5%) between
+	/// 2000K and 12500K.
+	pub fn to_kelvin(&self) -> f32 {
+		let n = (self.x - 0.332) / (0.1858 - self.y);
+		449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+	}
+
+	pub fn to_mirek(&self) -> u32 {
+		mirek_from_kelvin(self.to_kelvin())
+	}
+}
+
+impl MirekSchema {
+	pub fn contains(&self, mirek: u32) -> bool {
+		(self.mirek_minimum..=self.mirek_maximum).contains(&mirek)
+	}
+
+	pub fn clamp(&self, mirek: u32) -> u32 {
+		mirek.clamp(self.mirek_minimum, self.mirek_maximum)
+	}
+}
+
+impl Temperature {
+	/// Color temperature in Kelvin, if the light is in color temperature mode.
+	pub fn kelvin(&self) -> Option<f32> {
+		match (self.mirek, self.mirek_valid) {
+			(Some(mirek), true) => Some(kelvin_from_mirek(mirek)),
+			_ => None,
+		}
+	}
+
+	/// Color of the temperature, like for a preview swatch, if the light is in color temperature mode.
+	pub fn xy(&self) -> Option<Component> {
+		match (self.mirek, self.mirek_valid) {
+			(Some(mirek), true) => Some(Component::from_mirek(mirek)),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::assert_component_eq;
+
+	#[test]
+	fn kelvin_to_xy() {
+		assert_component_eq!(
+			Component::unchecked(0.5267, 0.4133),
+			Component::from_kelvin(2000.0),
+			0.001
+		);
+		assert_component_eq!(
+			Component::unchecked(0.4599, 0.4106),
+			Component::from_kelvin(2700.0),
+			0.001
+		);
+		assert_component_eq!(
+			Component::unchecked(0.3135, 0.3237),
+			Component::from_kelvin(6500.0),
+			0.001
+		);
+		assert_component_eq!(
+			Component::from_kelvin(KELVIN_MIN),
+			Component::from_kelvin(1000.0),
+			0.0001
+		);
+	}
+
+	#[test]
+	fn xy_to_kelvin() {
+		for kelvin in [2000.0, 2700.0, 4000.0, 5000.0, 6500.0, 10000.0] {
+			let estimated = Component::from_kelvin(kelvin).to_kelvin();
+			assert!(
+				(estimated - kelvin).abs() / kelvin < 0.015,
+				"{}K estimated as {}K",
+				kelvin,
+				estimated
+			);
+		}
+
+		assert_eq!(mirek_from_kelvin(2000.0), 500);
+		assert!((Component::from_mirek(153).to_mirek() as i64 - 153).abs() <= 1);
+	}
+
+	#[test]
+	fn temperature() {
+		let temperature: Temperature = serde_json::from_str(
+			r#"{"mirek": 366, "mirek_valid": true, "mirek_schema": {"mirek_minimum": 153, "mirek_maximum": 500}}"#,
+		)
+		.unwrap();
+
+		assert!((temperature.kelvin().unwrap() - 2732.24).abs() < 0.01);
+		assert_component_eq!(Component::from_mirek(366), temperature.xy().unwrap(), 0.0001);
+		assert!(temperature.mirek_schema.contains(153));
+		assert_eq!(temperature.mirek_schema.clamp(100), 153);
+	}
+}
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
-use crate::models::devices::GetDevicesResponseItem;
+use crate::http::HueError;
+use crate::models::devices::{DeviceIdentifyRequest, GetDevicesResponseItem, IdentifyAction};
 use crate::models::generic::{GenericIdentifier, ProductData};
-use crate::Hue;
+use crate::{http, Hue};
 
 pub type Devices = Vec<Device>;
 
@@ -25,4 +26,23 @@ impl Device {
 			services: device.services,
 		}
 	}
+
+	/// Makes the device blink or breathe once, so it can be located.
+	pub async fn identify(&self) -> Result<(), HueError> {
+		let url = self.hue.url(format!("clip/v2/resource/device/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = DeviceIdentifyRequest::new(IdentifyAction::Identify);
+
+		http::put_auth(self.hue.transport(), application_key, url, &request_payload)
+			.await
+			.map(|_| ())
+	}
+
+	/// Removes the device from the bridge, unpairing it. Its services are removed along with it.
+	pub async fn delete(self) -> Result<(), HueError> {
+		let url = self.hue.url(format!("clip/v2/resource/device/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+
+		http::delete_auth(self.hue.transport(), application_key, url).await
+	}
 }
@@ -1,11 +1,31 @@
-use std::collections::HashSet;
-use std::net::Ipv4Addr;
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, UdpSocket};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use mdns_sd::{ServiceDaemon, ServiceEvent};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Semaphore;
+
+use crate::bridge::Model;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::{http, models, Bridge, Hue};
 
 const SERVICE_NAME: &str = "_hue._tcp.local.";
+/// Port bridges serve the API on, unless they advertise another.
+const BRIDGE_PORT: u16 = 443;
+
+/// Smallest prefix accepted for a subnet scan, so a typo doesn't end up probing millions of addresses.
+const SUBNET_PREFIX_MIN: u8 = 16;
+/// How long to wait for a discovered bridge configuration, before settling with what the discovery source reported.
+const CONFIG_TIMEOUT: Duration = Duration::from_secs(3);
+/// Maximum amount of addresses probed at the same time during a subnet scan.
+const SUBNET_SCAN_CONCURRENCY: usize = 64;
+
+/// How often the mDNS browser checks whether the discovery was dropped.
+const MDNS_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 #[derive(Serialize, Deserialize)]
 pub struct Discovery {
@@ -13,59 +33,593 @@ pub struct Discovery {
 
 	#[serde(rename = "internalipaddress")]
 	pub internal_ip_address: String,
+	#[serde(default = "bridge_port")]
 	pub port: u16,
 }
 
-pub async fn discover(timeout: Duration) -> HashSet<Ipv4Addr> {
-	discover_mdns(timeout)
-		.await
-		.into_iter()
-		.chain(discover_meethue().await)
-		.collect()
-}
-
-async fn discover_mdns(timeout: Duration) -> HashSet<Ipv4Addr> {
-	let result = tokio::spawn(async move {
-		let mut ips = HashSet::new();
-
-		if let Ok(mdns) = ServiceDaemon::new() {
-			if let Ok(receiver) = mdns.browse(SERVICE_NAME) {
-				let end_time = std::time::SystemTime::now() + timeout;
-				while std::time::SystemTime::now() < end_time {
-					if let Ok(event) = receiver.recv_timeout(Duration::from_secs(1)) {
-						match event {
-							ServiceEvent::ServiceResolved(info) => {
-								info.get_addresses().iter().for_each(|ip| drop(ips.insert(ip.to_std())))
-							},
-							_ => (),
-						}
-					}
+/// Mechanisms through which bridges can be discovered.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DiscoverySource {
+	/// Multicast DNS, in the local network.
+	Mdns,
+	/// [discovery.meethue.com](https://discovery.meethue.com), which requires internet access.
+	Meethue,
+	/// Probing every address of a subnet, for networks without multicast nor internet access. Slow, so it is opt-in.
+	Subnet(SubnetScan),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SubnetError {
+	Invalid,
+	Prefix,
+}
+
+/// An IPv4 subnet in CIDR notation, like `192.168.1.0/24`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Subnet {
+	pub address: Ipv4Addr,
+	pub prefix: u8,
+}
+
+/// Subnet scan parameters.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SubnetScan {
+	pub subnet: Subnet,
+	pub port: u16,
+	/// Whether to probe through HTTPS, as bridges do, or plain HTTP.
+	pub secure: bool,
+	/// How long to wait for each address to answer.
+	pub timeout: Duration,
+}
+
+/// A bridge as reported by a discovery source, before its configuration is read.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DiscoveredBridge {
+	pub address: Ipv4Addr,
+	/// Port the bridge advertised its API on.
+	pub port: u16,
+	pub id: Option<String>,
+	pub model: Option<Model>,
+	pub source: DiscoverySource,
+}
+
+/// Bridges discovered in the background, yielded as soon as they are resolved.
+///
+/// Each bridge is yielded only once, even if found through multiple addresses or sources. Discovery stops when the
+/// stream is dropped.
+pub struct BridgeStream {
+	receiver: UnboundedReceiver<Bridge>,
+}
+
+#[derive(Clone)]
+struct Discoverer {
+	sender: UnboundedSender<Bridge>,
+	addresses: Arc<Mutex<HashSet<Ipv4Addr>>>,
+	ids: Arc<Mutex<HashSet<String>>>,
+	/// Transport the bridge configurations are read through.
+	transport: Arc<dyn Transport>,
+}
+
+impl Subnet {
+	pub fn new(address: Ipv4Addr, prefix: u8) -> Result<Subnet, SubnetError> {
+		if !(SUBNET_PREFIX_MIN..=32).contains(&prefix) {
+			return Err(SubnetError::Prefix);
+		}
+
+		Ok(Subnet { address, prefix })
+	}
+
+	/// The /24 subnet of the interface used to reach the local network, if any.
+	pub fn local() -> Option<Subnet> {
+		// Connecting a UDP socket sends nothing, but picks the interface (and address) routing to the destination.
+		let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+		socket.connect((Ipv4Addr::new(192, 168, 0, 1), 9)).ok()?;
+		match socket.local_addr().ok()?.ip() {
+			std::net::IpAddr::V4(ip) if !ip.is_unspecified() => Subnet::new(ip, 24).ok(),
+			_ => None,
+		}
+	}
+
+	/// Every host address in the subnet, skipping the network and broadcast addresses when the subnet has them.
+	pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> {
+		let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+		let network = u32::from(self.address) & mask;
+		let broadcast = network | !mask;
+
+		let (first, last) = if self.prefix >= 31 {
+			(network, broadcast)
+		} else {
+			(network + 1, broadcast - 1)
+		};
+
+		(first..=last).map(Ipv4Addr::from)
+	}
+}
+
+impl FromStr for Subnet {
+	type Err = SubnetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (address, prefix) = s.split_once('/').ok_or(SubnetError::Invalid)?;
+		Subnet::new(
+			address.parse().map_err(|_| SubnetError::Invalid)?,
+			prefix.parse().map_err(|_| SubnetError::Invalid)?,
+		)
+	}
+}
+
+impl SubnetScan {
+	/// Scans `subnet` for bridges on the default HTTPS port, waiting up to a second for each address.
+	pub fn new(subnet: Subnet) -> SubnetScan {
+		SubnetScan {
+			subnet,
+			port: BRIDGE_PORT,
+			secure: true,
+			timeout: Duration::from_secs(1),
+		}
+	}
+
+	/// Scans the local /24 subnet, if there is one.
+	pub fn local() -> Option<SubnetScan> {
+		Subnet::local().map(SubnetScan::new)
+	}
+
+	fn url(&self, ip: Ipv4Addr) -> String {
+		let scheme = if self.secure { "https" } else { "http" };
+		format!("{}://{}:{}/api/0/config", scheme, ip, self.port)
+	}
+}
+
+impl DiscoveredBridge {
+	/// Reads the `bridgeid` and `modelid` TXT records of a `_hue._tcp` service.
+	pub fn from_mdns(address: Ipv4Addr, port: u16, properties: &HashMap<String, String>) -> DiscoveredBridge {
+		DiscoveredBridge {
+			address,
+			port,
+			// Bridge identifiers are lower case in the TXT records, but upper case in the configuration.
+			id: properties.get("bridgeid").map(|id| id.to_uppercase()),
+			model: properties.get("modelid").map(Model::from),
+			source: DiscoverySource::Mdns,
+		}
+	}
+
+	fn from_meethue(discovery: Discovery) -> Option<DiscoveredBridge> {
+		Some(DiscoveredBridge {
+			address: discovery.internal_ip_address.parse().ok()?,
+			port: discovery.port,
+			id: Some(discovery.id.to_uppercase()),
+			model: None,
+			source: DiscoverySource::Meethue,
+		})
+	}
+}
+
+impl DiscoverySource {
+	pub const ALL: [DiscoverySource; 2] = [DiscoverySource::Mdns, DiscoverySource::Meethue];
+}
+
+impl BridgeStream {
+	/// Waits for the next bridge, returning `None` once every source is exhausted.
+	pub async fn next(&mut self) -> Option<Bridge> {
+		self.receiver.recv().await
+	}
+
+	pub async fn collect(mut self) -> Vec<Bridge> {
+		let mut bridges = Vec::new();
+		while let Some(bridge) = self.next().await {
+			bridges.push(bridge);
+		}
+
+		bridges
+	}
+}
+
+impl Discoverer {
+	/// Discoverer yielding to the returned stream, which ends once every clone of the discoverer is dropped.
+	fn new(transport: Arc<dyn Transport>) -> (Discoverer, BridgeStream) {
+		let (sender, receiver) = unbounded_channel();
+		let discoverer = Discoverer {
+			sender,
+			addresses: Arc::new(Mutex::new(HashSet::new())),
+			ids: Arc::new(Mutex::new(HashSet::new())),
+			transport,
+		};
+
+		(discoverer, BridgeStream { receiver })
+	}
+
+	fn is_closed(&self) -> bool {
+		self.sender.is_closed()
+	}
+
+	/// Reads the configuration of `discovered` in the background, unless its address was already probed or the bridge
+	/// was already yielded.
+	///
+	/// If the configuration can't be read in time, the bridge is still yielded with whatever the source reported.
+	fn found(&self, discovered: DiscoveredBridge) {
+		if self.is_closed() || !self.addresses.lock().unwrap().insert(discovered.address) {
+			return;
+		}
+		if let Some(id) = &discovered.id {
+			if self.ids.lock().unwrap().contains(id) {
+				return;
+			}
+		}
+
+		let discoverer = self.clone();
+		tokio::spawn(async move {
+			let ip = discovered.address;
+			match tokio::time::timeout(CONFIG_TIMEOUT, Hue::get_config(discoverer.transport.as_ref(), &ip)).await {
+				Ok(Some(config)) => discoverer.resolved(ip, config, discovered.source),
+				_ if discovered.id.is_some() => discoverer.send(Bridge::from(&discovered)),
+				_ => (),
+			}
+		});
+	}
+
+	fn resolved(&self, ip: Ipv4Addr, config: models::Config, source: DiscoverySource) {
+		self.send(Bridge {
+			source: Some(source),
+			..Bridge::from((ip, config))
+		});
+	}
+
+	/// Yields `bridge`, unless it was already yielded.
+	fn send(&self, bridge: Bridge) {
+		if self.ids.lock().unwrap().insert(bridge.id.clone()) {
+			// The stream being dropped is the only reason this would fail, and that is fine.
+			let _ = self.sender.send(bridge);
+		}
+	}
+}
+
+/// Starts discovering bridges through `sources`. mDNS browsing goes on for `timeout`.
+///
+/// Must be called from within a Tokio runtime.
+pub fn discover_stream(sources: &[DiscoverySource], timeout: Duration) -> BridgeStream {
+	let (discoverer, stream) = Discoverer::new(Arc::new(ReqwestTransport::new()));
+
+	for source in sources.iter().collect::<HashSet<_>>() {
+		let discoverer = discoverer.clone();
+		match source {
+			DiscoverySource::Mdns => {
+				let runtime = tokio::runtime::Handle::current();
+				tokio::task::spawn_blocking(move || {
+					let _guard = runtime.enter();
+					browse_mdns(timeout, &discoverer)
+				});
+			},
+			DiscoverySource::Meethue => {
+				tokio::spawn(async move {
+					discover_meethue()
+						.await
+						.into_iter()
+						.for_each(|bridge| discoverer.found(bridge));
+				});
+			},
+			DiscoverySource::Subnet(scan) => {
+				let scan = *scan;
+				tokio::spawn(async move { scan_subnet(scan, &discoverer).await });
+			},
+		}
+	}
+
+	stream
+}
+
+fn browse_mdns(timeout: Duration, discoverer: &Discoverer) {
+	if let Ok(mdns) = ServiceDaemon::new() {
+		if let Ok(receiver) = mdns.browse(SERVICE_NAME) {
+			let end_time = std::time::SystemTime::now() + timeout;
+			while std::time::SystemTime::now() < end_time && !discoverer.is_closed() {
+				if let Ok(ServiceEvent::ServiceResolved(info)) = receiver.recv_timeout(MDNS_POLL_INTERVAL) {
+					info.get_addresses().iter().for_each(|ip| {
+						discoverer.found(DiscoveredBridge::from_mdns(
+							ip.to_std(),
+							info.get_port(),
+							info.get_properties(),
+						))
+					});
 				}
 			}
+		}
 
-			// If shutdown fails there is not much we can do, so just accept it.
-			drop(mdns.shutdown());
+		// If shutdown fails there is not much we can do, so just accept it.
+		drop(mdns.shutdown());
+	}
+}
+
+async fn scan_subnet(scan: SubnetScan, discoverer: &Discoverer) {
+	let client = http::build();
+	let semaphore = Arc::new(Semaphore::new(SUBNET_SCAN_CONCURRENCY));
+
+	let mut probes = Vec::new();
+	for ip in scan.subnet.hosts() {
+		let permit = match semaphore.clone().acquire_owned().await {
+			Ok(permit) => permit,
+			Err(_) => break,
+		};
+		if discoverer.is_closed() {
+			break;
+		}
+		if !discoverer.addresses.lock().unwrap().insert(ip) {
+			continue;
 		}
 
-		ips
-	})
-	.await;
+		let client = client.clone();
+		let discoverer = discoverer.clone();
+		probes.push(tokio::spawn(async move {
+			if let Some(config) = probe_subnet_address(&client, &scan, ip).await {
+				discoverer.resolved(ip, config, DiscoverySource::Subnet(scan));
+			}
+			drop(permit);
+		}));
+	}
 
-	match result {
-		Ok(ips) => ips,
-		Err(_) => HashSet::new(),
+	for probe in probes {
+		drop(probe.await);
 	}
 }
 
-async fn discover_meethue() -> HashSet<Ipv4Addr> {
+async fn probe_subnet_address(client: &reqwest::Client, scan: &SubnetScan, ip: Ipv4Addr) -> Option<models::Config> {
+	let config = client
+		.get(scan.url(ip))
+		.timeout(scan.timeout)
+		.send()
+		.await
+		.ok()?
+		.json::<models::Config>()
+		.await
+		.ok()?;
+
+	// Other devices may answer the same endpoint, but only bridges have a bridge identifier and a bridge model.
+	if !config.bridgeid.is_empty() && config.modelid.starts_with("BSB") {
+		Some(config)
+	} else {
+		None
+	}
+}
+
+fn bridge_port() -> u16 {
+	BRIDGE_PORT
+}
+
+async fn discover_meethue() -> Vec<DiscoveredBridge> {
 	if let Ok(response) = reqwest::get("https://discovery.meethue.com").await {
 		if let Ok(bridges) = response.json::<Vec<Discovery>>().await {
-			return bridges
-				.into_iter()
-				.filter_map(|bridge| bridge.internal_ip_address.parse().ok())
-				.collect();
+			return bridges.into_iter().filter_map(DiscoveredBridge::from_meethue).collect();
+		}
+	}
+
+	Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+	use tokio::net::TcpListener;
+
+	use super::*;
+	use crate::hue::tests::CONFIG;
+	use crate::transport::{MemoryTransport, Method};
+
+	/// Serves `body` as JSON to every request, like a bridge (or anything else) would.
+	async fn stand_in(listener: TcpListener, body: &'static str) {
+		while let Ok((mut stream, _)) = listener.accept().await {
+			let mut buffer = [0u8; 1024];
+			drop(stream.read(&mut buffer).await);
+			let response = format!(
+				"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: \
+				 close\r\n\r\n{}",
+				body.len(),
+				body
+			);
+			drop(stream.write_all(response.as_bytes()).await);
 		}
 	}
 
-	HashSet::new()
+	#[test]
+	fn subnet_from_str() {
+		let subnet = Subnet::from_str("192.168.1.77/24").unwrap();
+		assert_eq!(subnet.hosts().count(), 254);
+		assert_eq!(subnet.hosts().next(), Some(Ipv4Addr::new(192, 168, 1, 1)));
+		assert_eq!(subnet.hosts().last(), Some(Ipv4Addr::new(192, 168, 1, 254)));
+
+		assert_eq!(Subnet::from_str("10.0.0.1/32").unwrap().hosts().count(), 1);
+		assert_eq!(Subnet::from_str("10.0.0.0/8"), Err(SubnetError::Prefix));
+		assert_eq!(Subnet::from_str("10.0.0.0"), Err(SubnetError::Invalid));
+	}
+
+	#[test]
+	fn subnet_hosts() {
+		let hosts: Vec<Ipv4Addr> = Subnet::new(Ipv4Addr::new(10, 0, 0, 6), 30).unwrap().hosts().collect();
+		assert_eq!(hosts, vec![Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(10, 0, 0, 6)]);
+
+		let hosts: Vec<Ipv4Addr> = Subnet::new(Ipv4Addr::new(192, 168, 7, 200), 24)
+			.unwrap()
+			.hosts()
+			.collect();
+		assert_eq!(hosts.len(), 254);
+		assert!(!hosts.contains(&Ipv4Addr::new(192, 168, 7, 0)));
+		assert!(!hosts.contains(&Ipv4Addr::new(192, 168, 7, 255)));
+		assert!(hosts
+			.iter()
+			.zip(1u8..)
+			.all(|(host, last)| *host == Ipv4Addr::new(192, 168, 7, last)));
+
+		// without room for network and broadcast addresses, every address is a host.
+		assert_eq!(Subnet::new(Ipv4Addr::new(10, 0, 0, 4), 31).unwrap().hosts().count(), 2);
+	}
+
+	#[test]
+	fn discovered_bridge_from_mdns() {
+		let properties = HashMap::from([
+			("bridgeid".to_owned(), "001788fffe000000".to_owned()),
+			("modelid".to_owned(), "BSB002".to_owned()),
+		]);
+		let discovered = DiscoveredBridge::from_mdns(Ipv4Addr::new(192, 168, 0, 2), 8443, &properties);
+
+		assert_eq!(discovered.port, 8443);
+		assert_eq!(discovered.id.as_deref(), Some("001788FFFE000000"));
+		assert_eq!(discovered.model, Some(Model::BSB002));
+		assert_eq!(discovered.source, DiscoverySource::Mdns);
+
+		let bridge = Bridge::from(&discovered);
+		assert_eq!(bridge.id, "001788FFFE000000");
+		assert_eq!(bridge.address, Ipv4Addr::new(192, 168, 0, 2));
+		assert_eq!(bridge.source, Some(DiscoverySource::Mdns));
+		assert!(!bridge.supported);
+	}
+
+	#[test]
+	fn discovered_bridge_from_meethue() {
+		let discoveries: Vec<Discovery> = serde_json::from_str(
+			r#"[
+				{"id": "001788fffe000000", "internalipaddress": "192.168.0.2", "port": 8443},
+				{"id": "001788fffe000001", "internalipaddress": "192.168.0.3"}
+			]"#,
+		)
+		.unwrap();
+		let discovered: Vec<DiscoveredBridge> = discoveries
+			.into_iter()
+			.filter_map(DiscoveredBridge::from_meethue)
+			.collect();
+
+		assert_eq!(discovered[0].id.as_deref(), Some("001788FFFE000000"));
+		assert_eq!(discovered[0].port, 8443);
+		assert_eq!(discovered[1].port, 443);
+		assert_eq!(discovered[1].source, DiscoverySource::Meethue);
+	}
+
+	/// Discoverer reading every configuration from [`CONFIG`], so every address is the same bridge.
+	fn discoverer() -> (Arc<MemoryTransport>, Discoverer, BridgeStream) {
+		let transport = Arc::new(MemoryTransport::new());
+		transport.respond(Method::Get, "/api/0/config", 200, CONFIG);
+		let (discoverer, bridges) = Discoverer::new(transport.clone());
+
+		(transport, discoverer, bridges)
+	}
+
+	fn discovered(address: Ipv4Addr, id: Option<&str>, source: DiscoverySource) -> DiscoveredBridge {
+		DiscoveredBridge {
+			address,
+			port: BRIDGE_PORT,
+			id: id.map(str::to_owned),
+			model: None,
+			source,
+		}
+	}
+
+	#[tokio::test]
+	async fn discoverer_merges_by_id() {
+		let (transport, discoverer, bridges) = discoverer();
+		let id = Some("001788FFFE000000");
+
+		discoverer.found(discovered(Ipv4Addr::new(192, 168, 0, 2), id, DiscoverySource::Mdns));
+		discoverer.found(discovered(Ipv4Addr::new(192, 168, 0, 3), id, DiscoverySource::Meethue));
+		drop(discoverer);
+
+		let bridges = bridges.collect().await;
+		assert_eq!(bridges.len(), 1);
+		assert_eq!(bridges[0].id, "001788FFFE000000");
+		// both addresses are probed, as neither was resolved when the other was found.
+		assert_eq!(transport.requests().len(), 2);
+	}
+
+	#[tokio::test]
+	async fn discoverer_merges_by_address() {
+		let (transport, discoverer, bridges) = discoverer();
+		let address = Ipv4Addr::new(192, 168, 0, 2);
+
+		discoverer.found(discovered(address, None, DiscoverySource::Mdns));
+		discoverer.found(discovered(address, None, DiscoverySource::Meethue));
+		drop(discoverer);
+
+		let bridges = bridges.collect().await;
+		assert_eq!(bridges.len(), 1);
+		assert_eq!(bridges[0].address, address);
+		assert_eq!(bridges[0].source, Some(DiscoverySource::Mdns));
+		assert_eq!(transport.requests().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn discoverer_streams_before_sources_finish() {
+		let (_, discoverer, mut bridges) = discoverer();
+
+		let mdns = discoverer.clone();
+		tokio::spawn(async move {
+			mdns.found(discovered(Ipv4Addr::new(192, 168, 0, 2), None, DiscoverySource::Mdns));
+		});
+		// a source which never completes, like a browse waiting for more answers.
+		let meethue = discoverer.clone();
+		tokio::spawn(async move {
+			meethue.found(discovered(
+				Ipv4Addr::new(192, 168, 0, 3),
+				Some("001788FFFE000000"),
+				DiscoverySource::Meethue,
+			));
+			std::future::pending::<()>().await;
+		});
+		drop(discoverer);
+
+		let bridge = tokio::time::timeout(Duration::from_secs(1), bridges.next())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(bridge.id, "001788FFFE000000");
+
+		// the same bridge isn't yielded again, but the stream stays open while a source goes on.
+		assert!(tokio::time::timeout(Duration::from_millis(200), bridges.next())
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn discover_stream_without_sources() {
+		let mut bridges = discover_stream(&[], Duration::from_secs(5));
+		assert!(bridges.next().await.is_none());
+	}
+
+	#[tokio::test]
+	async fn discover_stream_subnet() {
+		let bridge = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+		let bridge_port = bridge.local_addr().unwrap().port();
+		let other = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+		let other_port = other.local_addr().unwrap().port();
+
+		tokio::spawn(stand_in(
+			bridge,
+			r#"{"name": "Hue Bridge", "datastoreversion": "131", "swversion": "1950111030", "apiversion": "1.50.0",
+			"mac": "00:17:88:00:00:00", "bridgeid": "001788FFFE000000", "factorynew": false,
+			"replacesbridgeid": null, "modelid": "BSB002", "starterkitid": ""}"#,
+		));
+		tokio::spawn(stand_in(
+			other,
+			r#"{"name": "Router", "datastoreversion": "1", "swversion": "1", "apiversion": "1", "mac": "",
+			"bridgeid": "", "factorynew": false, "replacesbridgeid": null, "modelid": "RT-1", "starterkitid": null}"#,
+		));
+
+		// Only the loopback address is always available, so each stand-in gets its own scan.
+		let scan = |port: u16| {
+			SubnetScan {
+				subnet: Subnet::from_str("127.0.0.1/32").unwrap(),
+				port,
+				secure: false,
+				timeout: Duration::from_millis(500),
+			}
+		};
+
+		let bridges = discover_stream(&[DiscoverySource::Subnet(scan(other_port))], Duration::from_secs(5))
+			.collect()
+			.await;
+		assert!(bridges.is_empty());
+
+		let bridges = discover_stream(&[DiscoverySource::Subnet(scan(bridge_port))], Duration::from_secs(5))
+			.collect()
+			.await;
+		assert_eq!(bridges.len(), 1);
+		assert_eq!(bridges[0].id, "001788FFFE000000");
+		assert_eq!(bridges[0].address, Ipv4Addr::LOCALHOST);
+		assert_eq!(bridges[0].source, Some(DiscoverySource::Subnet(scan(bridge_port))));
+	}
 }
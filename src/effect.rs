@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+	NoEffect,
+	Candle,
+	Fire,
+	Prism,
+	Sparkle,
+	Opal,
+	Glisten,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimedEffect {
+	NoEffect,
+	Sunrise,
+	Sunset,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertAction {
+	Breathe,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+	NoSignal,
+	OnOff,
+	OnOffColor,
+	Alternating,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Effects {
+	pub status: Effect,
+	pub status_values: Vec<Effect>,
+	pub effect_values: Vec<Effect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedEffects {
+	pub status: TimedEffect,
+	pub status_values: Vec<TimedEffect>,
+	pub effect_values: Vec<TimedEffect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+	pub action_values: Vec<AlertAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalingStatus {
+	pub signal: Signal,
+	pub estimated_end: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signaling {
+	pub signal_values: Vec<Signal>,
+	pub status: Option<SignalingStatus>,
+}
+
+impl Effects {
+	/// Whether the light supports `effect`. [`Effect::Unknown`] never is, as it can't be sent back to the bridge.
+	pub fn supports(&self, effect: Effect) -> bool {
+		effect != Effect::Unknown && self.effect_values.contains(&effect)
+	}
+}
+
+impl TimedEffects {
+	/// Whether the light supports `effect`. [`TimedEffect::Unknown`] never is, as it can't be sent back to the bridge.
+	pub fn supports(&self, effect: TimedEffect) -> bool {
+		effect != TimedEffect::Unknown && self.effect_values.contains(&effect)
+	}
+}
+
+impl Alert {
+	/// Whether the light supports `action`. [`AlertAction::Unknown`] never is, as it can't be sent back to the bridge.
+	pub fn supports(&self, action: AlertAction) -> bool {
+		action != AlertAction::Unknown && self.action_values.contains(&action)
+	}
+}
+
+impl Signaling {
+	/// Whether the light supports `signal`. [`Signal::Unknown`] never is, as it can't be sent back to the bridge.
+	pub fn supports(&self, signal: Signal) -> bool {
+		signal != Signal::Unknown && self.signal_values.contains(&signal)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn effects_deserialize() {
+		let effects: Effects = serde_json::from_str(
+			r#"{
+				"status": "candle",
+				"status_values": ["no_effect", "candle", "fire", "some_future_effect"],
+				"effect_values": ["no_effect", "candle", "fire", "some_future_effect"]
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(effects.status, Effect::Candle);
+		assert_eq!(effects.status_values[3], Effect::Unknown);
+		assert!(effects.supports(Effect::Fire));
+		assert!(!effects.supports(Effect::Prism));
+		assert_eq!(effects.effect_values[3], Effect::Unknown);
+		assert!(!effects.supports(Effect::Unknown));
+	}
+
+	#[test]
+	fn timed_effects_deserialize() {
+		let timed_effects: TimedEffects = serde_json::from_str(
+			r#"{
+				"status": "no_effect",
+				"status_values": ["no_effect", "sunrise"],
+				"effect_values": ["no_effect", "sunrise"]
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(timed_effects.status, TimedEffect::NoEffect);
+		assert!(timed_effects.supports(TimedEffect::Sunrise));
+		assert!(!timed_effects.supports(TimedEffect::Sunset));
+	}
+}
@@ -0,0 +1,244 @@
+//! Extraction of the dominant colors of an image, to match lights to it (like to an album art or a wallpaper).
+//!
+//! Requires the `extract` feature.
+//! ```no_run
+//! # use huehue::{HueError, Light};
+//! # use huehue::extract;
+//! #
+//! # async fn match_lights(mut lights: Vec<Light>, pixels: &[u8]) -> Result<(), HueError> {
+//! let colors = extract::dominant_colors(pixels, lights.len());
+//! extract::assign(&mut lights, &colors).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::color::{Component, Gamut, GradientMode, RGB8};
+use crate::{HueError, Light};
+
+/// Pixels whose brightest channel is below this are ignored, as lights can't show them.
+const DARK_MAX: u8 = 20;
+
+/// A dominant color of an image.
+#[derive(Debug, Clone)]
+pub struct Dominant {
+	pub rgb: RGB8,
+	/// Share of the (non dark) pixels of the image close to the color, between 0 and 1.
+	pub weight: f32,
+}
+
+impl Dominant {
+	/// Color restrained to `gamut`, and brightness percentage, as taken by [`Light::set_color`] and [`Light::dimm`].
+	pub fn xy(&self, gamut: &Gamut) -> (Component, f32) {
+		gamut.xy_from_rgb8_with_brightness(self.rgb)
+	}
+}
+
+/// Extracts up to `count` dominant colors of an image, the most dominant first.
+///
+/// Colors are clustered through median cut, refined by k-means.
+///
+/// `pixels` hold the red, green and blue channels of each pixel, in order; any trailing incomplete pixel is ignored.
+/// Dark pixels are ignored, so images which are mostly dark get the colors of their few lit parts.
+pub fn dominant_colors(pixels: &[u8], count: usize) -> Vec<Dominant> {
+	let pixels: Vec<[u8; 3]> = pixels
+		.chunks_exact(3)
+		.map(|pixel| [pixel[0], pixel[1], pixel[2]])
+		.collect();
+	median_cut(pixels, count)
+}
+
+/// Like [`dominant_colors`], with pixels as [`RGB8`].
+pub fn dominant_colors_rgb8(pixels: &[RGB8], count: usize) -> Vec<Dominant> {
+	median_cut(pixels.iter().map(|pixel| [pixel.r, pixel.g, pixel.b]).collect(), count)
+}
+
+/// Channel with the widest range of values among `pixels`, and that range.
+fn widest_channel(pixels: &[[u8; 3]]) -> (usize, u8) {
+	(0..3)
+		.map(|channel| {
+			let min = pixels.iter().map(|pixel| pixel[channel]).min().unwrap_or(0);
+			let max = pixels.iter().map(|pixel| pixel[channel]).max().unwrap_or(0);
+			(channel, max - min)
+		})
+		.max_by_key(|(_, range)| *range)
+		.unwrap()
+}
+
+fn median_cut(pixels: Vec<[u8; 3]>, count: usize) -> Vec<Dominant> {
+	let pixels: Vec<[u8; 3]> = pixels
+		.into_iter()
+		.filter(|pixel| pixel.iter().any(|c| *c >= DARK_MAX))
+		.collect();
+	if pixels.is_empty() || count == 0 {
+		return Vec::new();
+	}
+
+	let total = pixels.len();
+	let mut boxes = vec![pixels];
+	while boxes.len() < count {
+		// splits the box with the widest range at its median.
+		let (index, channel, range) = boxes
+			.iter()
+			.enumerate()
+			.map(|(index, pixels)| {
+				let (channel, range) = widest_channel(pixels);
+				(index, channel, range)
+			})
+			.max_by_key(|(_, _, range)| *range)
+			.unwrap();
+		if range == 0 {
+			break;
+		}
+
+		let mut pixels = boxes.swap_remove(index);
+		pixels.sort_unstable_by_key(|pixel| pixel[channel]);
+		let upper = pixels.split_off(pixels.len() / 2);
+		boxes.push(pixels);
+		boxes.push(upper);
+	}
+
+	let centroids: Vec<[f32; 3]> = boxes.iter().map(|pixels| average(pixels.iter())).collect();
+	let pixels: Vec<[u8; 3]> = boxes.into_iter().flatten().collect();
+	let (centroids, counts) = k_means(&pixels, centroids);
+
+	let mut colors: Vec<Dominant> = centroids
+		.into_iter()
+		.zip(counts)
+		.filter(|(_, count)| *count > 0)
+		.map(|(centroid, count)| {
+			let [r, g, b] = centroid.map(|c| c.round() as u8);
+			Dominant {
+				rgb: RGB8::new(r, g, b),
+				weight: count as f32 / total as f32,
+			}
+		})
+		.collect();
+	colors.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+	colors
+}
+
+fn average<'a>(pixels: impl Iterator<Item = &'a [u8; 3]>) -> [f32; 3] {
+	let (sum, count) = pixels.fold(([0u64; 3], 0u64), |(mut sum, count), pixel| {
+		for channel in 0..3 {
+			sum[channel] += pixel[channel] as u64;
+		}
+		(sum, count + 1)
+	});
+
+	sum.map(|c| c as f32 / count.max(1) as f32)
+}
+
+fn nearest(pixel: &[u8; 3], centroids: &[[f32; 3]]) -> usize {
+	let distance2 = |centroid: &[f32; 3]| -> f32 { (0..3).map(|c| (pixel[c] as f32 - centroid[c]).powi(2)).sum() };
+
+	(0..centroids.len())
+		.min_by(|a, b| distance2(&centroids[*a]).total_cmp(&distance2(&centroids[*b])))
+		.unwrap()
+}
+
+/// Refines the median cut `centroids` through k-means, which moves pixels split away from their cluster back to it.
+/// Returns the centroids and how many pixels are closest to each.
+fn k_means(pixels: &[[u8; 3]], mut centroids: Vec<[f32; 3]>) -> (Vec<[f32; 3]>, Vec<usize>) {
+	const ITERATIONS_MAX: usize = 10;
+
+	let mut assignments: Vec<usize> = pixels.iter().map(|pixel| nearest(pixel, &centroids)).collect();
+	for _ in 0..ITERATIONS_MAX {
+		for (index, centroid) in centroids.iter_mut().enumerate() {
+			let members = pixels
+				.iter()
+				.zip(&assignments)
+				.filter(|(_, assigned)| **assigned == index);
+			if members.clone().next().is_some() {
+				*centroid = average(members.map(|(pixel, _)| pixel));
+			}
+		}
+
+		let updated: Vec<usize> = pixels.iter().map(|pixel| nearest(pixel, &centroids)).collect();
+		if updated == assignments {
+			break;
+		}
+		assignments = updated;
+	}
+
+	let mut counts = vec![0; centroids.len()];
+	for assigned in assignments {
+		counts[assigned] += 1;
+	}
+
+	(centroids, counts)
+}
+
+/// Sets the color and brightness of each light to one of `colors`, in order, cycling through them if there are less
+/// colors than lights. Lights without color are skipped.
+pub async fn assign(lights: &mut [Light], colors: &[Dominant]) -> Result<(), HueError> {
+	if colors.is_empty() {
+		return Ok(());
+	}
+
+	for (light, color) in lights
+		.iter_mut()
+		.filter(|light| light.color.is_some())
+		.zip(colors.iter().cycle())
+	{
+		let (xy, brightness) = color.xy(&light.color.as_ref().unwrap().gamut);
+		light.set_color(xy).await?;
+		if light.brightness.is_some() {
+			light.dimm(brightness).await?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Sets the gradient of `light` to as many of `colors` as it supports, the most dominant first. A single color is
+/// repeated.
+pub async fn assign_gradient(light: &mut Light, colors: &[Dominant], mode: GradientMode) -> Result<(), HueError> {
+	let points_capable = match &light.gradient {
+		Some(gradient) => gradient.points_capable,
+		None => return Err(HueError::Unsupported),
+	};
+
+	let colors: Vec<RGB8> = match colors {
+		[] => return Err(HueError::Unsupported),
+		[color] => vec![color.rgb, color.rgb],
+		colors => colors.iter().take(points_capable).map(|color| color.rgb).collect(),
+	};
+
+	light.set_gradient_rgb(&colors, mode).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn dominant() {
+		let mut pixels = Vec::new();
+		pixels.extend([[250u8, 10, 10]; 50].iter().flatten());
+		pixels.extend([[10u8, 10, 240]; 30].iter().flatten());
+		pixels.extend([[10u8, 230, 10]; 20].iter().flatten());
+		pixels.extend([[0u8, 0, 0]; 100].iter().flatten());
+
+		let colors = dominant_colors(&pixels, 3);
+		assert_eq!(colors.len(), 3);
+		assert_eq!((colors[0].rgb.r, colors[0].rgb.g, colors[0].rgb.b), (250, 10, 10));
+		assert!((colors[0].weight - 0.5).abs() < 0.0001);
+		assert_eq!((colors[1].rgb.r, colors[1].rgb.g, colors[1].rgb.b), (10, 10, 240));
+		assert_eq!((colors[2].rgb.r, colors[2].rgb.g, colors[2].rgb.b), (10, 230, 10));
+
+		// no more colors than there are in the image.
+		assert_eq!(dominant_colors(&pixels, 10).len(), 3);
+		assert!(dominant_colors(&[0, 0, 0], 3).is_empty());
+	}
+
+	#[test]
+	fn dominant_xy() {
+		let colors = dominant_colors_rgb8(&[RGB8::new(255, 0, 0), RGB8::new(128, 0, 0)], 1);
+		let (xy, brightness) = colors[0].xy(&Gamut::C);
+
+		assert_eq!((colors[0].rgb.r, colors[0].rgb.g, colors[0].rgb.b), (192, 0, 0));
+		assert!((brightness - 192.0 / 255.0 * 100.0).abs() < 0.001);
+		crate::assert_component_eq!(Gamut::C.xy_from_rgb8(RGB8::new(255, 0, 0)), xy, 0.0001);
+	}
+}
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use crate::http::HueError;
+use crate::models::bridge::{GetBridgeHomeResponseItem, GetBridgeResponseItem};
+use crate::models::generic::GenericIdentifier;
+use crate::models::lights::LightOnRequest;
+use crate::{http, Hue};
+
+/// The whole house, as seen by the bridge: every room, and the devices not assigned to any room.
+#[derive(Debug, Clone)]
+pub struct Home {
+	pub hue: Hue,
+	pub id: uuid::Uuid,
+	pub bridge_id: String,
+	pub time_zone: String,
+	pub children: HashSet<GenericIdentifier>,
+
+	/// Grouped light controlling every light in the house.
+	pub grouped_light: Option<uuid::Uuid>,
+}
+
+impl Home {
+	pub fn new(hue: &Hue, bridge: GetBridgeResponseItem, home: GetBridgeHomeResponseItem) -> Home {
+		Home {
+			hue: hue.clone(),
+			id: home.id,
+			bridge_id: bridge.bridge_id,
+			time_zone: bridge.time_zone.time_zone,
+			children: home.children,
+			grouped_light: home
+				.services
+				.into_iter()
+				.find(|service| service.rtype == "grouped_light")
+				.map(|service| service.rid),
+		}
+	}
+
+	pub fn rooms(&self) -> impl Iterator<Item = &uuid::Uuid> {
+		self.children
+			.iter()
+			.filter(|child| child.rtype == "room")
+			.map(|child| &child.rid)
+	}
+
+	/// Switches every light in the house on or off.
+	pub async fn switch(&self, on: bool) -> Result<(), HueError> {
+		let grouped_light = match &self.grouped_light {
+			Some(grouped_light) => grouped_light,
+			None => return Err(HueError::Unsupported),
+		};
+
+		let url = self
+			.hue
+			.url(format!("clip/v2/resource/grouped_light/{}", grouped_light).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightOnRequest::new(on);
+
+		http::put_auth(self.hue.transport(), application_key, url, &request_payload)
+			.await
+			.map(|_| ())
+	}
+}
@@ -1,28 +1,47 @@
-use reqwest::header::HeaderMap;
-use reqwest::{Certificate, Client, ClientBuilder, Error, StatusCode};
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+use reqwest::ClientBuilder;
+use reqwest::{Client, Error, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use url::Url;
 
-use crate::certificate::CERTIFICATE;
+use crate::color::ParseColorError;
 use crate::models::error::ErrorCode;
+use crate::models::generic::{ClipResponse, GenericIdentifier};
+use crate::transport::{Response, Transport};
 
-const HUE_APPLICATION_KEY_HEADER: &str = "hue-application-key";
+pub const HUE_APPLICATION_KEY_HEADER: &str = "hue-application-key";
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("Either the `native-tls` or the `rustls` feature must be enabled.");
 
 #[derive(Debug)]
 pub enum HueError {
 	Unauthorized,
 	AlreadyAuthorized,
 	Connection,
+	NotFound,
 	Response(reqwest::Error),
+	Decode(serde_json::Error),
 	Unsupported,
+	InvalidColor(ParseColorError),
+	/// Errors reported by the bridge, by their description.
+	Bridge(Vec<String>),
 	Unexpected,
 	Unknown,
 }
 
+#[cfg(feature = "rustls")]
+fn build_base() -> ClientBuilder {
+	reqwest::Client::builder().use_preconfigured_tls(crate::certificate::rustls_config())
+}
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
 fn build_base() -> ClientBuilder {
+	use crate::certificate::CERTIFICATE;
+
 	reqwest::Client::builder()
-		.add_root_certificate(Certificate::from_pem(CERTIFICATE.as_bytes()).unwrap())
+		.add_root_certificate(reqwest::Certificate::from_pem(CERTIFICATE.as_bytes()).unwrap())
 		.danger_accept_invalid_hostnames(true)
 }
 
@@ -30,74 +49,145 @@ pub fn build() -> Client {
 	build_base().build().unwrap()
 }
 
-pub fn build_with_key(application_key: String) -> Client {
-	let mut headers = HeaderMap::new();
-	headers.insert(HUE_APPLICATION_KEY_HEADER, application_key.parse().unwrap());
-
-	build_base().default_headers(headers).build().unwrap()
+fn decode<R>(response: Response) -> Result<R, HueError>
+where
+	R: DeserializeOwned,
+{
+	match serde_json::from_str::<R>(response.body.as_str()) {
+		Ok(payload) => Ok(payload),
+		Err(e) => {
+			match StatusCode::from_u16(response.status) {
+				Ok(StatusCode::UNAUTHORIZED) | Ok(StatusCode::FORBIDDEN) => Err(HueError::Unauthorized),
+				Ok(StatusCode::NOT_FOUND) => Err(HueError::NotFound),
+				_ => Err(HueError::Decode(e)),
+			}
+		},
+	}
 }
 
-pub async fn get_auth<R>(application_key: String, url: Url) -> Result<R, HueError>
+/// Decodes a CLIP v2 response, succeeding only if the bridge reported no errors.
+fn decode_clip<R>(response: Response) -> Result<Vec<R>, HueError>
 where
 	R: DeserializeOwned,
 {
-	let client = build_with_key(application_key);
+	let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+	if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+		return Err(HueError::Unauthorized);
+	}
 
-	let response = match client.get(url).send().await {
-		Ok(response) => response,
-		Err(e) => return Err(HueError::from(e)),
-	};
+	let payload: ClipResponse<R> = decode(response)?;
+	if !payload.errors.is_empty() {
+		return Err(HueError::Bridge(
+			payload.errors.into_iter().map(|error| error.description).collect(),
+		));
+	}
 
-	match response.json::<R>().await {
-		Ok(payload) => Ok(payload),
-		Err(e) => Err(HueError::from(e)),
+	match status {
+		status if status.is_success() => Ok(payload.data),
+		StatusCode::NOT_FOUND => Err(HueError::NotFound),
+		_ => Err(HueError::Unexpected),
 	}
 }
 
-#[allow(unused)]
-pub async fn get_auth_text(application_key: String, url: Url) -> Result<String, HueError> {
-	let client = build_with_key(application_key);
-
-	let response = match client.get(url).send().await {
-		Ok(response) => response,
-		Err(e) => return Err(HueError::from(e)),
-	};
+fn encode<T>(object: &T) -> Result<String, HueError>
+where
+	T: Serialize,
+{
+	serde_json::to_string(object).map_err(HueError::Decode)
+}
 
-	match response.text().await {
-		Ok(payload) => Ok(payload),
-		Err(e) => Err(HueError::from(e)),
-	}
+pub async fn get<R>(transport: &dyn Transport, url: Url) -> Result<R, HueError>
+where
+	R: DeserializeOwned,
+{
+	decode(transport.get(url, None).await?)
 }
 
-pub async fn put_auth<R, T>(application_key: String, url: Url, object: &T) -> Result<R, HueError>
+pub async fn post<R, T>(transport: &dyn Transport, url: Url, object: &T) -> Result<R, HueError>
 where
 	T: Serialize,
 	R: DeserializeOwned,
 {
-	let client = build_with_key(application_key);
+	decode(transport.post(url, None, encode(object)?).await?)
+}
+
+pub async fn get_auth<R>(transport: &dyn Transport, application_key: String, url: Url) -> Result<Vec<R>, HueError>
+where
+	R: DeserializeOwned,
+{
+	decode_clip(transport.get(url, Some(application_key)).await?)
+}
 
-	let response = match client.put(url).json(&object).send().await {
-		Ok(response) => response,
-		Err(e) => return Err(HueError::from(e)),
-	};
+#[allow(unused)]
+pub async fn get_auth_text(transport: &dyn Transport, application_key: String, url: Url) -> Result<String, HueError> {
+	Ok(transport.get(url, Some(application_key)).await?.body)
+}
 
-	match response.json::<R>().await {
-		Ok(payload) => Ok(payload),
-		Err(e) => Err(HueError::from(e)),
+/// Succeeds only if the bridge reported the resource targeted by `url` (the last path segment) among `identifiers`,
+/// as a success without it doesn't mean the resource was changed.
+fn confirm(url: &Url, identifiers: Vec<GenericIdentifier>) -> Result<Vec<GenericIdentifier>, HueError> {
+	let rid = url
+		.path_segments()
+		.and_then(|mut segments| segments.next_back())
+		.and_then(|segment| uuid::Uuid::parse_str(segment).ok());
+
+	match rid {
+		Some(rid) if identifiers.iter().any(|identifier| identifier.rid == rid) => Ok(identifiers),
+		_ => Err(HueError::NotFound),
 	}
 }
 
+/// Updates a resource, returning the identifiers of the affected resources, which must include it.
+pub async fn put_auth<T>(
+	transport: &dyn Transport,
+	application_key: String,
+	url: Url,
+	object: &T,
+) -> Result<Vec<GenericIdentifier>, HueError>
+where
+	T: Serialize,
+{
+	let response = transport
+		.put(url.clone(), Some(application_key), encode(object)?)
+		.await?;
+	confirm(&url, decode_clip(response)?)
+}
+
+/// Creates a resource, returning its identifier.
+pub async fn post_auth<T>(
+	transport: &dyn Transport,
+	application_key: String,
+	url: Url,
+	object: &T,
+) -> Result<GenericIdentifier, HueError>
+where
+	T: Serialize,
+{
+	let response = transport.post(url, Some(application_key), encode(object)?).await?;
+	decode_clip::<GenericIdentifier>(response)?
+		.into_iter()
+		.next()
+		.ok_or(HueError::Unexpected)
+}
+
+/// Deletes a resource, succeeding only if the bridge reported it as deleted.
+pub async fn delete_auth(transport: &dyn Transport, application_key: String, url: Url) -> Result<(), HueError> {
+	let response = transport.delete(url.clone(), Some(application_key)).await?;
+	confirm(&url, decode_clip(response)?).map(|_| ())
+}
+
 impl From<Error> for HueError {
 	fn from(e: Error) -> Self {
 		if e.is_status() {
 			match e.status().unwrap() {
 				StatusCode::UNAUTHORIZED => HueError::Unauthorized,
+				StatusCode::NOT_FOUND => HueError::NotFound,
 				_ => HueError::Unknown,
 			}
-		} else if e.is_connect() {
-			return HueError::Connection;
+		} else if e.is_connect() || e.is_timeout() {
+			HueError::Connection
 		} else if e.is_decode() {
-			return HueError::Response(e);
+			HueError::Response(e)
 		} else {
 			HueError::Unknown
 		}
@@ -109,6 +199,7 @@ impl From<ErrorCode> for HueError {
 		match ec {
 			ErrorCode::Unauthorized => HueError::Unauthorized,
 			ErrorCode::LinkButtonNotPressed => HueError::Unauthorized,
+			ErrorCode::ResourceNotAvailable => HueError::NotFound,
 			_ => HueError::Unknown,
 		}
 	}
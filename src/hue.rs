@@ -1,40 +1,55 @@
 use std::net::Ipv4Addr;
+use std::sync::Arc;
 use std::time::Duration;
 
+use serde::Serialize;
 use url::Url;
 
 use crate::device::{Device, Devices};
+use crate::discover::{BridgeStream, DiscoverySource};
 use crate::http::HueError;
 use crate::light::Lights;
+use crate::models::bridge::{GetBridgeHomeResponseItem, GetBridgeResponseItem};
 use crate::models::create_user::{CreateUserRequest, CreateUserResponse};
 use crate::models::device_type::DeviceType;
-use crate::models::devices::GetDevicesResponse;
-use crate::models::lights::GetLightsResponse;
-use crate::{discover, http, models, Bridge, Light};
+use crate::models::devices::GetDevicesResponseItem;
+use crate::models::generic::{GenericIdentifier, Resource};
+use crate::models::lights::GetLightsResponseItem;
+use crate::recording::RecordingTransport;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::{discover, http, models, Bridge, Home, Light};
 
 #[derive(Debug, Clone)]
 pub struct Hue {
 	bridge: Bridge,
 	device_type: DeviceType,
 	application_key: Option<String>,
+	transport: Arc<dyn Transport>,
 }
 
 impl Hue {
 	pub async fn new(ip: Ipv4Addr, device_type: DeviceType) -> Result<Hue, HueError> {
-		let bridge = match Self::get_config(&ip).await {
-			Some(config) => Bridge::from((ip, config)),
-			None => return Err(HueError::Connection),
-		};
+		Self::new_with_transport(ip, device_type, None, Arc::new(ReqwestTransport::new())).await
+	}
 
-		Ok(Hue {
-			bridge,
+	pub async fn new_with_key(ip: Ipv4Addr, device_type: DeviceType, application_key: String) -> Result<Hue, HueError> {
+		Self::new_with_transport(
+			ip,
 			device_type,
-			application_key: None,
-		})
+			Some(application_key),
+			Arc::new(ReqwestTransport::new()),
+		)
+		.await
 	}
 
-	pub async fn new_with_key(ip: Ipv4Addr, device_type: DeviceType, application_key: String) -> Result<Hue, HueError> {
-		let bridge = match Self::get_config(&ip).await {
+	/// Connects to the bridge through `transport`, instead of the default [`ReqwestTransport`].
+	pub async fn new_with_transport(
+		ip: Ipv4Addr,
+		device_type: DeviceType,
+		application_key: Option<String>,
+		transport: Arc<dyn Transport>,
+	) -> Result<Hue, HueError> {
+		let bridge = match Self::get_config(transport.as_ref(), &ip).await {
 			Some(config) => Bridge::from((ip, config)),
 			None => return Err(HueError::Connection),
 		};
@@ -42,7 +57,8 @@ impl Hue {
 		Ok(Hue {
 			bridge,
 			device_type,
-			application_key: Some(application_key),
+			application_key,
+			transport,
 		})
 	}
 
@@ -51,7 +67,7 @@ impl Hue {
 	}
 
 	pub fn url(&self, path: &str) -> url::Url {
-		Url::parse(format!("https://{}/{}", self.bridge.address.to_string(), path).as_str()).unwrap()
+		Url::parse(format!("https://{}/{}", self.bridge.address, path).as_str()).unwrap()
 	}
 
 	pub fn device_type(&self) -> &DeviceType {
@@ -62,17 +78,40 @@ impl Hue {
 		self.application_key.clone()
 	}
 
-	pub async fn bridges(timeout: Duration) -> Vec<Bridge> {
-		let ips = discover::discover(timeout).await;
-		let mut bridges = Vec::new();
-		for ip in ips {
-			match Self::get_config(&ip).await {
-				Some(config) => bridges.push(Bridge::from((ip, config))),
-				None => (),
-			}
+	pub(crate) fn transport(&self) -> &dyn Transport {
+		self.transport.as_ref()
+	}
+
+	/// Starts recording every request sent to the bridge, and its response, with the application key redacted.
+	///
+	/// The bridge configuration is read again, so the recording is enough to build a [`Hue`] on a
+	/// [`ReplayTransport`](crate::recording::ReplayTransport). Only requests sent through this [`Hue`], or through
+	/// resources obtained from it afterwards, are recorded.
+	pub async fn record(&mut self) -> Result<Arc<RecordingTransport>, HueError> {
+		let recording = Arc::new(RecordingTransport::new(self.transport.clone()));
+		if let Some(application_key) = &self.application_key {
+			recording.redact_key(application_key.as_str());
+		}
+
+		match Self::get_config(recording.as_ref(), &self.bridge.address).await {
+			Some(_) => {
+				self.transport = recording.clone();
+				Ok(recording)
+			},
+			None => Err(HueError::Connection),
 		}
+	}
 
-		bridges
+	/// Discovers bridges through every [`DiscoverySource`], waiting for the whole `timeout`.
+	pub async fn bridges(timeout: Duration) -> Vec<Bridge> {
+		Self::discover_stream(&DiscoverySource::ALL, timeout).collect().await
+	}
+
+	/// Discovers bridges through `sources`, yielding each bridge as soon as it is resolved.
+	///
+	/// Must be called from within a Tokio runtime.
+	pub fn discover_stream(sources: &[DiscoverySource], timeout: Duration) -> BridgeStream {
+		discover::discover_stream(sources, timeout)
 	}
 
 	fn check_authorization(&self) -> Result<(), HueError> {
@@ -83,17 +122,9 @@ impl Hue {
 		}
 	}
 
-	async fn get_config(ip: &Ipv4Addr) -> Option<models::Config> {
-		let url = Url::parse(format!("https://{}/api/0/config", ip.to_string()).as_str()).unwrap();
-		let client = http::build();
-		client
-			.get(url.to_string())
-			.send()
-			.await
-			.ok()?
-			.json::<models::Config>()
-			.await
-			.ok()
+	pub(crate) async fn get_config(transport: &dyn Transport, ip: &Ipv4Addr) -> Option<models::Config> {
+		let url = Url::parse(format!("https://{}/api/0/config", ip).as_str()).unwrap();
+		http::get(transport, url).await.ok()
 	}
 
 	pub async fn authorize(&mut self) -> Result<(), HueError> {
@@ -103,20 +134,12 @@ impl Hue {
 
 		let request = CreateUserRequest::new(self.device_type.clone());
 
-		let response = match http::build().post(self.url("api")).json(&request).send().await {
-			Ok(response) => response,
-			Err(e) => return Err(HueError::from(e)),
-		};
-
-		let payload = match response.json::<CreateUserResponse>().await {
-			Ok(data) => data,
-			Err(e) => return Err(HueError::from(e)),
-		};
+		let payload: CreateUserResponse = http::post(self.transport(), self.url("api"), &request).await?;
 		if payload.len() != 1 {
 			return Err(HueError::Unexpected);
 		}
 
-		let data = payload.get(0).unwrap();
+		let data = payload.first().unwrap();
 		if let Some(data) = &data.success {
 			self.application_key = Some(data.username.to_owned());
 			return Ok(());
@@ -131,38 +154,298 @@ impl Hue {
 	pub async fn lights(&self) -> Result<Lights, HueError> {
 		self.check_authorization()?;
 
-		let response: GetLightsResponse = http::get_auth(
+		let data: Vec<GetLightsResponseItem> = http::get_auth(
+			self.transport(),
 			self.application_key.clone().unwrap(),
 			self.url("clip/v2/resource/light"),
 		)
 		.await?;
 
-		if let Some(data) = response.data {
-			return Ok(data.into_iter().map(|datum| Light::new(self, datum)).collect());
-		}
-		if let Some(error) = response.error {
-			return Err(HueError::from(error.r#type.clone()));
-		}
-
-		Err(HueError::Unknown)
+		Ok(data.into_iter().map(|datum| Light::new(self, datum)).collect())
 	}
 
 	pub async fn devices(&self) -> Result<Devices, HueError> {
 		self.check_authorization()?;
 
-		let response: GetDevicesResponse = http::get_auth(
+		let data: Vec<GetDevicesResponseItem> = http::get_auth(
+			self.transport(),
 			self.application_key.clone().unwrap(),
 			self.url("clip/v2/resource/device"),
 		)
 		.await?;
 
-		if let Some(data) = response.data {
-			return Ok(data.into_iter().map(|datum| Device::new(self, datum)).collect());
+		Ok(data.into_iter().map(|datum| Device::new(self, datum)).collect())
+	}
+
+	/// Fetches a single resource of type `T` by its identifier.
+	///
+	/// Returns [`HueError::NotFound`] if the bridge has no such resource.
+	pub async fn get<T>(&self, id: uuid::Uuid) -> Result<T, HueError>
+	where
+		T: Resource,
+	{
+		self.check_authorization()?;
+
+		let data: Vec<T> = http::get_auth(
+			self.transport(),
+			self.application_key.clone().unwrap(),
+			self.url(format!("clip/v2/resource/{}/{}", T::TYPE, id).as_str()),
+		)
+		.await?;
+
+		data.into_iter().next().ok_or(HueError::NotFound)
+	}
+
+	/// Creates a resource of type `T` from `payload`, returning its identifier.
+	///
+	/// Returns [`HueError::Bridge`] with the bridge's descriptions if it rejects the payload.
+	pub async fn create<T, P>(&self, payload: &P) -> Result<GenericIdentifier, HueError>
+	where
+		T: Resource,
+		P: Serialize,
+	{
+		self.check_authorization()?;
+
+		http::post_auth(
+			self.transport(),
+			self.application_key.clone().unwrap(),
+			self.url(format!("clip/v2/resource/{}", T::TYPE).as_str()),
+			payload,
+		)
+		.await
+	}
+
+	/// Fetches every resource of type `T`.
+	async fn get_all<T>(&self) -> Result<Vec<T>, HueError>
+	where
+		T: Resource,
+	{
+		self.check_authorization()?;
+
+		http::get_auth(
+			self.transport(),
+			self.application_key.clone().unwrap(),
+			self.url(format!("clip/v2/resource/{}", T::TYPE).as_str()),
+		)
+		.await
+	}
+
+	/// Fetches the bridge and its home, which holds every room and controls every light in the house.
+	pub async fn home(&self) -> Result<Home, HueError> {
+		let bridge = self.get_all::<GetBridgeResponseItem>().await?.into_iter().next();
+		let home = self.get_all::<GetBridgeHomeResponseItem>().await?.into_iter().next();
+
+		match (bridge, home) {
+			(Some(bridge), Some(home)) => Ok(Home::new(self, bridge, home)),
+			_ => Err(HueError::NotFound),
 		}
-		if let Some(error) = response.error {
-			return Err(HueError::from(error.r#type.clone()));
+	}
+
+	pub async fn light(&self, id: uuid::Uuid) -> Result<Light, HueError> {
+		let datum = self.get::<GetLightsResponseItem>(id).await?;
+		Ok(Light::new(self, datum))
+	}
+
+	pub async fn device(&self, id: uuid::Uuid) -> Result<Device, HueError> {
+		let datum = self.get::<GetDevicesResponseItem>(id).await?;
+		Ok(Device::new(self, datum))
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::transport::{MemoryTransport, Method};
+
+	pub(crate) const CONFIG: &str = r#"{
+		"name": "Hue Bridge", "datastoreversion": "131", "swversion": "1950111030", "apiversion": "1.50.0",
+		"mac": "00:17:88:00:00:00", "bridgeid": "001788FFFE000000", "factorynew": false, "replacesbridgeid": null,
+		"modelid": "BSB002", "starterkitid": ""
+	}"#;
+	pub(crate) const LIGHT_ID: &str = "3f5c2a5e-0f4d-4a5e-8b0b-0b3b7d1c9a11";
+	pub(crate) const LIGHT: &str = r#"{
+		"id": "3f5c2a5e-0f4d-4a5e-8b0b-0b3b7d1c9a11",
+		"owner": {"rid": "6b8e0e9a-1c3d-4b6a-9a4e-2f1d3c5b7a90", "rtype": "device"},
+		"metadata": {"name": "Desk", "archetype": "classic_bulb"},
+		"on": {"on": true},
+		"type": "light"
+	}"#;
+
+	pub(crate) const DEVICE_ID: &str = "6b8e0e9a-1c3d-4b6a-9a4e-2f1d3c5b7a90";
+	pub(crate) const DEVICE: &str = r#"{
+		"id": "6b8e0e9a-1c3d-4b6a-9a4e-2f1d3c5b7a90",
+		"metadata": {"name": "Desk", "archetype": "classic_bulb"},
+		"product_data": {
+			"certified": true, "model_id": "LCA001", "manufacturer_name": "Signify Netherlands B.V.",
+			"product_archetype": "sultan_bulb", "product_name": "Hue color lamp", "software_version": "1.93.11"
+		},
+		"services": [{"rid": "3f5c2a5e-0f4d-4a5e-8b0b-0b3b7d1c9a11", "rtype": "light"}],
+		"type": "device"
+	}"#;
+
+	/// Hue connected to a bridge served by `transport`, authorized with the key `key`.
+	pub(crate) async fn hue(transport: Arc<MemoryTransport>) -> Hue {
+		transport.respond(Method::Get, "/api/0/config", 200, CONFIG);
+		let device_type = DeviceType::new("huehue".to_owned(), "test".to_owned()).unwrap();
+		Hue::new_with_transport(Ipv4Addr::LOCALHOST, device_type, Some("key".to_owned()), transport)
+			.await
+			.unwrap()
+	}
+
+	#[tokio::test]
+	async fn lights() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		transport.respond(
+			Method::Get,
+			"/clip/v2/resource/light",
+			200,
+			format!(r#"{{"errors": [], "data": [{}]}}"#, LIGHT).as_str(),
+		);
+
+		let lights = hue.lights().await.unwrap();
+		assert_eq!(lights.len(), 1);
+		assert_eq!(lights[0].name, "Desk");
+
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.application_key.as_deref(), Some("key"));
+	}
+
+	#[tokio::test]
+	async fn lights_unauthorized() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		transport.respond(
+			Method::Get,
+			"/clip/v2/resource/light",
+			403,
+			r#"{"errors": [{"description": "unauthorized user"}], "data": []}"#,
+		);
+
+		assert!(matches!(hue.lights().await, Err(HueError::Unauthorized)));
+	}
+
+	#[tokio::test]
+	async fn record_authorize() {
+		let transport = Arc::new(MemoryTransport::new());
+		transport.respond(Method::Get, "/api/0/config", 200, CONFIG);
+		transport.respond(
+			Method::Post,
+			"/api",
+			200,
+			r#"[{"success": {"username": "created-key"}}]"#,
+		);
+		let device_type = DeviceType::new("huehue".to_owned(), "test".to_owned()).unwrap();
+		let mut hue = Hue::new_with_transport(Ipv4Addr::LOCALHOST, device_type, None, transport)
+			.await
+			.unwrap();
+
+		let recording = hue.record().await.unwrap();
+		hue.authorize().await.unwrap();
+		assert_eq!(hue.application_key().as_deref(), Some("created-key"));
+
+		let path = std::env::temp_dir().join(format!("huehue-record-authorize-{}.json", std::process::id()));
+		recording.save(&path).unwrap();
+		let fixture = std::fs::read_to_string(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert!(fixture.contains("/api"));
+		assert!(!fixture.contains("created-key"));
+	}
+
+	#[tokio::test]
+	async fn device_delete() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		let path = format!("/clip/v2/resource/device/{}", DEVICE_ID);
+		transport.respond(
+			Method::Get,
+			path.as_str(),
+			200,
+			format!(r#"{{"errors": [], "data": [{}]}}"#, DEVICE).as_str(),
+		);
+		transport.respond(
+			Method::Delete,
+			path.as_str(),
+			400,
+			r#"{"errors": [{"description": "device is in use"}], "data": []}"#,
+		);
+
+		let id = uuid::Uuid::parse_str(DEVICE_ID).unwrap();
+		match hue.device(id).await.unwrap().delete().await {
+			Err(HueError::Bridge(descriptions)) => assert_eq!(descriptions, vec!["device is in use".to_owned()]),
+			other => panic!("Unexpected result: {:?}", other),
 		}
 
-		Err(HueError::Unknown)
+		transport.respond(
+			Method::Delete,
+			path.as_str(),
+			200,
+			format!(
+				r#"{{"errors": [], "data": [{{"rid": "{}", "rtype": "device"}}]}}"#,
+				DEVICE_ID
+			)
+			.as_str(),
+		);
+		hue.device(id).await.unwrap().delete().await.unwrap();
+		assert_eq!(transport.requests().pop().unwrap().method, Method::Delete);
+	}
+
+	#[tokio::test]
+	async fn create() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		transport.respond(
+			Method::Post,
+			"/clip/v2/resource/device",
+			200,
+			format!(
+				r#"{{"errors": [], "data": [{{"rid": "{}", "rtype": "device"}}]}}"#,
+				DEVICE_ID
+			)
+			.as_str(),
+		);
+
+		let payload = serde_json::json!({"metadata": {"name": "Desk"}});
+		let identifier = hue.create::<GetDevicesResponseItem, _>(&payload).await.unwrap();
+		assert_eq!(identifier.rid, uuid::Uuid::parse_str(DEVICE_ID).unwrap());
+		assert_eq!(identifier.rtype, "device");
+
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.method, Method::Post);
+		assert_eq!(request.application_key.as_deref(), Some("key"));
+		assert_eq!(
+			serde_json::from_str::<serde_json::Value>(request.body.unwrap().as_str()).unwrap(),
+			payload
+		);
+	}
+
+	#[tokio::test]
+	async fn create_rejected() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		transport.respond(
+			Method::Post,
+			"/clip/v2/resource/device",
+			207,
+			r#"{"errors": [{"description": "invalid value for metadata"}], "data": []}"#,
+		);
+
+		match hue.create::<GetDevicesResponseItem, _>(&serde_json::json!({})).await {
+			Err(HueError::Bridge(descriptions)) => {
+				assert_eq!(descriptions, vec!["invalid value for metadata".to_owned()])
+			},
+			other => panic!("Unexpected result: {:?}", other),
+		}
+	}
+
+	#[tokio::test]
+	async fn light_not_found() {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+
+		assert!(matches!(
+			hue.light(uuid::Uuid::parse_str(LIGHT_ID).unwrap()).await,
+			Err(HueError::NotFound)
+		));
 	}
 }
@@ -5,18 +5,45 @@
 //!
 //! ## Features
 //! - Hue Bridge certificate validation.
+//! - TLS through native-tls (default) or rustls, selected by the `native-tls` and `rustls` features.
+//! - Pluggable HTTP transport, including an in-memory one for tests.
+//! - Recording of bridge traffic into fixture files, and their replay in tests.
 //! - Bridge discovery:
 //!   - through mDNS.
 //!   - through [discovery.meethue.com](https://discovery.meethue.com).
+//!   - scanning a subnet, when neither mDNS nor internet are available (opt-in).
 //!   - user specified IPv4 address.
+//! - Home:
+//!   - bridge identifier and time zone.
+//!   - list rooms.
+//!   - switch every light in the house on/off.
 //! - Devices:
 //!   - list devices.
+//!   - get a device by identifier.
+//!   - identify (blink) a device.
+//!   - delete (unpair) a device.
 //! - Light:
+//!   - list lights.
+//!   - get a light by identifier, and refresh its state.
 //!   - switch on/off.
+//!   - rename, change archetype and configure power up behaviour.
 //!   - color in the [CIE 1931 color space](https://en.wikipedia.org/wiki/CIE_1931_color_space).
 //!   - color in the sRGB color space.
+//!   - color in HSV, or as a hex string or CSS color name.
+//!   - white by color temperature, in Kelvin or mirek.
 //!   - dimming.
-//! - XY to RGB and RGB to XY conversion.
+//!   - gradients, for gradient lightstrips and Play gradient tubes, optionally from a palette.
+//!   - effects (candle, fire, etc.), timed effects (sunrise, sunset), alerts and signaling.
+//! - XY to RGB and RGB to XY conversion, optionally carrying brightness, in sRGB, Display P3, Rec.2020 or Adobe RGB,
+//!   from 8-bit, 16-bit or float channels.
+//! - Gamut mapping to the nearest point, preserving hue, or compressing chroma.
+//! - Color differences (CIE76 and CIEDE2000), closest reachable colors of a gamut, and names of colors.
+//! - Serde representations of colors for configuration files, as hex strings and `[x, y]` arrays.
+//! - Conversions between sRGB, HSV, HSL, CIE XYZ, CIE Lab, LCh and OKLab.
+//! - Conversions between color temperatures and XY.
+//! - Palettes interpolated in CIE Lab or OKLab, with built-in ones for scenes and gradients.
+//! - Blocking API, through the `blocking` feature.
+//! - Dominant color extraction from images, to match lights to them, through the `extract` feature.
 //!
 //! ## Discovery
 //! Bridges can be discovered by simply running:
@@ -31,6 +58,20 @@
 //!
 //! Both MDNS and [discovery.meehue.com](discovery.meehue.com) will be used during the discovery.
 //!
+//! To handle bridges as soon as they are found, and to choose the discovery sources, use a stream instead:
+//! ```no_run
+//! # use huehue::Hue;
+//! # use huehue::discover::DiscoverySource;
+//! # use std::time::Duration;
+//! #
+//! # async fn discover() {
+//! let mut bridges = Hue::discover_stream(&[DiscoverySource::Mdns], Duration::from_secs(5));
+//! while let Some(bridge) = bridges.next().await {
+//! 	println!("{}", bridge.id);
+//! }
+//! # }
+//! ```
+//!
 //! ## Authorization
 //! Once a bridge is chosen, you made need to generate an application key with it, so you can interact with it.
 //!
@@ -49,7 +90,9 @@
 //! let mut hue = Hue::new(ip, device_type)
 //! 	.await
 //! 	.expect("Failed to run bridge information.");
-//! hue.authorize().await.expect("Failed to link with the bridge.");
+//! hue.authorize()
+//! 	.await
+//! 	.expect("Failed to link with the bridge.");
 //! hue.application_key()
 //! 	.expect("When successfully authorized, this must always be valid.")
 //! # }
@@ -71,17 +114,29 @@
 //!
 //! With the resulting [`Hue`] instance you can interact with the bridge.
 
+#![allow(clippy::tabs_in_doc_comments)]
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod bridge;
 mod certificate;
 pub mod color;
 pub mod device;
-mod discover;
+pub mod discover;
+pub mod effect;
+#[cfg(feature = "extract")]
+pub mod extract;
+pub mod home;
 mod http;
 pub mod hue;
 pub mod light;
 pub mod models;
+pub mod powerup;
+pub mod recording;
+pub mod transport;
 
 pub use bridge::Bridge;
+pub use home::Home;
 pub use http::HueError;
 pub use hue::Hue;
 pub use light::Light;
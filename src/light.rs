@@ -1,7 +1,18 @@
-use crate::color::{Color, Component, Temperature, RGB8};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::color::{
+	mirek_from_kelvin, Color, Component, Gradient, GradientMode, GradientPoint, Palette, Temperature, HSV, RGB8, XYZ,
+};
+use crate::effect::{Alert, AlertAction, Effect, Effects, Signal, Signaling, TimedEffect, TimedEffects};
 use crate::http::HueError;
-use crate::models::lights::{GetLightsResponseItem, LightOnRequest, LightSetBrightnessRequest, LightSetColorRequest};
-use crate::models::GenericResponse;
+use crate::models::generic::{Archetype, Function, GenericIdentifier};
+use crate::models::lights::{
+	GetLightsResponseItem, LightAlertRequest, LightMode, LightOnRequest, LightSetBrightnessRequest,
+	LightSetColorRequest, LightSetEffectRequest, LightSetGradientRequest, LightSetMetadataRequest,
+	LightSetPowerupRequest, LightSetTemperatureRequest, LightSetTimedEffectRequest, LightSignalRequest,
+};
+use crate::powerup::Powerup;
 use crate::{http, Hue};
 
 pub type Lights = Vec<Light>;
@@ -10,11 +21,21 @@ pub type Lights = Vec<Light>;
 pub struct Light {
 	pub hue: Hue,
 	pub id: uuid::Uuid,
+	pub owner: GenericIdentifier,
 	pub name: String,
+	pub archetype: Archetype,
+	pub function: Option<Function>,
+	pub mode: Option<LightMode>,
 	pub on: bool,
 	pub brightness: Option<f32>,
 	pub color: Option<Color>,
 	pub temperature: Option<Temperature>,
+	pub gradient: Option<Gradient>,
+	pub effects: Option<Effects>,
+	pub timed_effects: Option<TimedEffects>,
+	pub alert: Option<Alert>,
+	pub signaling: Option<Signaling>,
+	pub powerup: Option<Powerup>,
 }
 
 impl Light {
@@ -22,11 +43,95 @@ impl Light {
 		Light {
 			hue: hue.clone(),
 			id: light.id,
+			owner: light.owner,
 			name: light.metadata.name,
+			archetype: light.metadata.archetype,
+			function: light.metadata.function,
+			mode: light.mode,
 			on: light.on.on,
 			brightness: light.dimming.map(|dimming| dimming.brightness),
 			color: light.color,
 			temperature: light.color_temperature,
+			gradient: light.gradient,
+			effects: light.effects,
+			timed_effects: light.timed_effects,
+			alert: light.alert,
+			signaling: light.signaling,
+			powerup: light.powerup,
+		}
+	}
+
+	/// Re-reads the light state from the bridge.
+	pub async fn refresh(&mut self) -> Result<(), HueError> {
+		let datum = self.hue.get::<GetLightsResponseItem>(self.id).await?;
+		*self = Light::new(&self.hue, datum);
+		Ok(())
+	}
+
+	pub async fn set_name(&mut self, name: String) -> Result<(), HueError> {
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetMetadataRequest::name(name.clone());
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				self.name = name;
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	pub async fn set_archetype(&mut self, archetype: Archetype) -> Result<(), HueError> {
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetMetadataRequest::archetype(archetype);
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				self.archetype = archetype;
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Configures what the light does when powered on. See [`Powerup`] for the available behaviours.
+	///
+	/// A powerup color is restrained to the light gamut, and is only accepted by lights with color, as a powerup
+	/// brightness is only accepted by dimmable lights.
+	pub async fn set_powerup(&mut self, mut powerup: Powerup) -> Result<(), HueError> {
+		if self.powerup.is_none() {
+			return Err(HueError::Unsupported);
+		}
+		if let Some(xy) = powerup.color.as_mut().and_then(|color| color.color.as_mut()) {
+			match &self.color {
+				Some(color) => xy.xy = color.gamut.restrain(&xy.xy),
+				None => return Err(HueError::Unsupported),
+			}
+		}
+		if powerup
+			.dimming
+			.as_ref()
+			.is_some_and(|dimming| dimming.dimming.is_some())
+			&& self.brightness.is_none()
+		{
+			return Err(HueError::Unsupported);
+		}
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetPowerupRequest::new(powerup.clone());
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				self.powerup = Some(Powerup {
+					configured: true,
+					..powerup
+				});
+				Ok(())
+			},
+			Err(e) => Err(e),
 		}
 	}
 
@@ -35,7 +140,7 @@ impl Light {
 		let application_key = self.hue.application_key().clone().unwrap();
 		let request_payload = LightOnRequest::new(on);
 
-		match http::put_auth::<GenericResponse, LightOnRequest>(application_key, url, &request_payload).await {
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
 			Ok(_) => {
 				self.on = on;
 				Ok(())
@@ -51,9 +156,9 @@ impl Light {
 
 		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
 		let application_key = self.hue.application_key().clone().unwrap();
-		let request_payload = LightSetColorRequest::new(component.clone());
+		let request_payload = LightSetColorRequest::new(component);
 
-		match http::put_auth::<GenericResponse, LightSetColorRequest>(application_key, url, &request_payload).await {
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
 			Ok(_) => {
 				if let Some(color) = &mut self.color {
 					color.xy = component;
@@ -73,6 +178,115 @@ impl Light {
 		}
 	}
 
+	/// Sets the hue and saturation of `hsv`, restrained to the light gamut. Its value is ignored, use [`Light::dimm`].
+	pub async fn set_color_hsv(&mut self, hsv: HSV) -> Result<(), HueError> {
+		let hsv = HSV { v: 1f32, ..hsv };
+		match (&self.color, XYZ::from(hsv).xy()) {
+			(Some(color), Some(xy)) => self.set_color(color.gamut.restrain(&xy)).await,
+			_ => Err(HueError::Unsupported),
+		}
+	}
+
+	/// Sets a color given as `#rrggbb` or as a CSS color name, restrained to the light gamut.
+	pub async fn set_color_hex(&mut self, hex: &str) -> Result<(), HueError> {
+		let rgb = RGB8::from_str(hex).map_err(HueError::InvalidColor)?;
+		self.set_color_rgb(rgb).await
+	}
+
+	/// Sets the color temperature, in mirek, clamped to the range supported by the light.
+	pub async fn set_temperature(&mut self, mirek: u32) -> Result<(), HueError> {
+		let mirek = match &self.temperature {
+			Some(temperature) => temperature.mirek_schema.clamp(mirek),
+			None => return Err(HueError::Unsupported),
+		};
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetTemperatureRequest::new(mirek);
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				if let Some(temperature) = &mut self.temperature {
+					temperature.mirek = Some(mirek);
+					temperature.mirek_valid = true;
+				}
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Sets a white of `kelvin`, through the color temperature of the light when it supports it, else through its
+	/// color, restrained to its gamut.
+	///
+	/// Lights supporting both get their color when `kelvin` is beyond their color temperature range.
+	pub async fn set_white(&mut self, kelvin: u32) -> Result<(), HueError> {
+		let mirek = mirek_from_kelvin(kelvin as f32);
+
+		match (&self.temperature, &self.color) {
+			(Some(temperature), Some(_)) if temperature.mirek_schema.contains(mirek) => {
+				self.set_temperature(mirek).await
+			},
+			(Some(_), None) => self.set_temperature(mirek).await,
+			(_, Some(color)) => {
+				let xy = color.gamut.restrain(&Component::from_kelvin(kelvin as f32));
+				self.set_color(xy).await
+			},
+			(None, None) => Err(HueError::Unsupported),
+		}
+	}
+
+	/// Sets the gradient of a gradient capable light (like lightstrips and Play gradient tubes).
+	///
+	/// The amount of `points` must be between [`Gradient::POINTS_MIN`] and the light's [`Gradient::points_capable`].
+	/// Every point is restrained to the light gamut.
+	pub async fn set_gradient(&mut self, points: &[Component], mode: GradientMode) -> Result<(), HueError> {
+		let (gradient, color) = match (&self.gradient, &self.color) {
+			(Some(gradient), Some(color)) => (gradient, color),
+			_ => return Err(HueError::Unsupported),
+		};
+		if points.len() < Gradient::POINTS_MIN || points.len() > gradient.points_capable || !gradient.supports(mode) {
+			return Err(HueError::Unsupported);
+		}
+
+		let points: Vec<Component> = points.iter().map(|point| color.gamut.restrain(point)).collect();
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetGradientRequest::new(points.clone(), mode);
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				if let Some(gradient) = &mut self.gradient {
+					gradient.points = points.into_iter().map(GradientPoint::new).collect();
+					gradient.mode = Some(mode);
+				}
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	pub async fn set_gradient_rgb(&mut self, colors: &[RGB8], mode: GradientMode) -> Result<(), HueError> {
+		if let Some(color) = &self.color {
+			let points: Vec<Component> = colors.iter().map(|rgb| color.gamut.xy_from_rgb8(*rgb)).collect();
+			self.set_gradient(&points, mode).await
+		} else {
+			Err(HueError::Unsupported)
+		}
+	}
+
+	/// Sets the gradient to as many colors of `palette` as the light supports.
+	pub async fn set_gradient_palette(&mut self, palette: &Palette, mode: GradientMode) -> Result<(), HueError> {
+		match (&self.gradient, &self.color) {
+			(Some(gradient), Some(color)) => {
+				let points = palette.sample_xy(gradient.points_capable, &color.gamut);
+				self.set_gradient(&points, mode).await
+			},
+			_ => Err(HueError::Unsupported),
+		}
+	}
+
 	pub async fn dimm(&mut self, value: f32) -> Result<(), HueError> {
 		if self.brightness.is_none() {
 			return Err(HueError::Unsupported);
@@ -80,10 +294,9 @@ impl Light {
 
 		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
 		let application_key = self.hue.application_key().clone().unwrap();
-		let request_payload = LightSetBrightnessRequest::new(value.clone());
+		let request_payload = LightSetBrightnessRequest::new(value);
 
-		match http::put_auth::<GenericResponse, LightSetBrightnessRequest>(application_key, url, &request_payload).await
-		{
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
 			Ok(_) => {
 				if let Some(brightness) = &mut self.brightness {
 					*brightness = value;
@@ -93,4 +306,343 @@ impl Light {
 			Err(e) => Err(e),
 		}
 	}
+
+	pub async fn set_effect(&mut self, effect: Effect) -> Result<(), HueError> {
+		match &self.effects {
+			Some(effects) if effects.supports(effect) => (),
+			_ => return Err(HueError::Unsupported),
+		}
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetEffectRequest::new(effect);
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				if let Some(effects) = &mut self.effects {
+					effects.status = effect;
+				}
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Starts a timed effect (like a sunrise), which takes `duration` to complete.
+	pub async fn set_timed_effect(&mut self, effect: TimedEffect, duration: Duration) -> Result<(), HueError> {
+		match &self.timed_effects {
+			Some(timed_effects) if timed_effects.supports(effect) => (),
+			_ => return Err(HueError::Unsupported),
+		}
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSetTimedEffectRequest::new(effect, duration);
+
+		match http::put_auth(self.hue.transport(), application_key, url, &request_payload).await {
+			Ok(_) => {
+				if let Some(timed_effects) = &mut self.timed_effects {
+					timed_effects.status = effect;
+				}
+				Ok(())
+			},
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Makes the light breathe once, so it can be spotted.
+	pub async fn alert(&self) -> Result<(), HueError> {
+		match &self.alert {
+			Some(alert) if alert.supports(AlertAction::Breathe) => (),
+			_ => return Err(HueError::Unsupported),
+		}
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightAlertRequest::new(AlertAction::Breathe);
+
+		http::put_auth(self.hue.transport(), application_key, url, &request_payload)
+			.await
+			.map(|_| ())
+	}
+
+	/// Signals the light for `duration`, so it can be spotted.
+	///
+	/// [`Signal::OnOffColor`] takes exactly one color, [`Signal::Alternating`] exactly two, and the other signals none.
+	/// Colors are restrained to the light gamut, and are only accepted by lights with color.
+	pub async fn signal(&self, signal: Signal, duration: Duration, colors: &[Component]) -> Result<(), HueError> {
+		match &self.signaling {
+			Some(signaling) if signaling.supports(signal) => (),
+			_ => return Err(HueError::Unsupported),
+		}
+
+		let colors_expected = match signal {
+			Signal::OnOffColor => 1,
+			Signal::Alternating => 2,
+			_ => 0,
+		};
+		if colors.len() != colors_expected {
+			return Err(HueError::Unsupported);
+		}
+
+		let colors = match (&self.color, colors.is_empty()) {
+			(_, true) => Vec::new(),
+			(Some(color), false) => colors.iter().map(|xy| color.gamut.restrain(xy)).collect(),
+			(None, false) => return Err(HueError::Unsupported),
+		};
+
+		let url = self.hue.url(format!("clip/v2/resource/light/{}", self.id).as_str());
+		let application_key = self.hue.application_key().clone().unwrap();
+		let request_payload = LightSignalRequest::new(signal, duration, colors);
+
+		http::put_auth(self.hue.transport(), application_key, url, &request_payload)
+			.await
+			.map(|_| ())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::*;
+	use crate::color::Gamut;
+	use crate::hue::tests::{hue, LIGHT, LIGHT_ID};
+	use crate::transport::{MemoryTransport, Method};
+
+	const COLOR: &str = r#""color": {"xy": {"x": 0.3, "y": 0.3}, "gamut_type": "C"}"#;
+	const DIMMING: &str = r#""dimming": {"brightness": 100.0, "min_dim_level": 0.2}"#;
+	const POWERUP: &str = r#""powerup": {"preset": "safety", "configured": true}"#;
+
+	/// Light with the fields of [`LIGHT`] and `fields`, fetched through the returned transport, which accepts every
+	/// update of the light.
+	async fn light_with(fields: &[&str]) -> (Arc<MemoryTransport>, Light) {
+		let transport = Arc::new(MemoryTransport::new());
+		let hue = hue(transport.clone()).await;
+		let path = format!("/clip/v2/resource/light/{}", LIGHT_ID);
+		let mut light = LIGHT.to_owned();
+		for fields in fields {
+			light = light.replace(r#""type": "light""#, format!(r#"{}, "type": "light""#, fields).as_str());
+		}
+		transport.respond(
+			Method::Get,
+			path.as_str(),
+			200,
+			format!(r#"{{"errors": [], "data": [{}]}}"#, light).as_str(),
+		);
+		transport.respond(
+			Method::Put,
+			path.as_str(),
+			200,
+			format!(
+				r#"{{"errors": [], "data": [{{"rid": "{}", "rtype": "light"}}]}}"#,
+				LIGHT_ID
+			)
+			.as_str(),
+		);
+
+		let light = hue.light(uuid::Uuid::parse_str(LIGHT_ID).unwrap()).await.unwrap();
+		(transport, light)
+	}
+
+	/// Whether the light was only read, and never updated.
+	fn only_read(transport: &MemoryTransport) -> bool {
+		transport.requests().iter().all(|request| request.method == Method::Get)
+	}
+
+	#[tokio::test]
+	async fn switch() {
+		let (transport, mut light) = light_with(&[]).await;
+
+		light.switch(false).await.unwrap();
+		assert!(!light.on);
+
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.method, Method::Put);
+		assert_eq!(request.body.as_deref(), Some(r#"{"on":{"on":false}}"#));
+	}
+
+	#[tokio::test]
+	async fn switch_rejected() {
+		let (transport, mut light) = light_with(&[]).await;
+		transport.respond(
+			Method::Put,
+			format!("/clip/v2/resource/light/{}", LIGHT_ID).as_str(),
+			207,
+			r#"{"errors": [{"description": "device (Desk) is \"soft off\", command (.on) may not have effect"}], "data": []}"#,
+		);
+
+		assert!(matches!(light.switch(false).await, Err(HueError::Bridge(descriptions)) if descriptions.len() == 1));
+		assert!(light.on);
+	}
+
+	#[tokio::test]
+	async fn switch_unconfirmed() {
+		let (transport, mut light) = light_with(&[]).await;
+		transport.respond(
+			Method::Put,
+			format!("/clip/v2/resource/light/{}", LIGHT_ID).as_str(),
+			200,
+			r#"{"errors":[],"data":[]}"#,
+		);
+
+		assert!(matches!(light.switch(false).await, Err(HueError::NotFound)));
+		assert!(light.on);
+	}
+
+	#[tokio::test]
+	async fn set_white() {
+		let (transport, mut light) = light_with(&[
+			COLOR,
+			r#""color_temperature": {
+				"mirek": null, "mirek_valid": false, "mirek_schema": {"mirek_minimum": 153, "mirek_maximum": 500}
+			}"#,
+		])
+		.await;
+
+		light.set_white(2700).await.unwrap();
+		assert_eq!(light.temperature.as_ref().unwrap().mirek, Some(370));
+		let request = transport.requests().pop().unwrap();
+		assert_eq!(request.body.as_deref(), Some(r#"{"color_temperature":{"mirek":370}}"#));
+
+		// beyond the color temperature range of the light.
+		light.set_white(10000).await.unwrap();
+		let request = transport.requests().pop().unwrap();
+		assert!(request.body.unwrap().starts_with(r#"{"color":{"xy":"#));
+	}
+
+	#[tokio::test]
+	async fn set_powerup() {
+		let (transport, mut light) = light_with(&[COLOR, DIMMING, POWERUP]).await;
+
+		// beyond the green of gamut C.
+		let green = Component::new(0.1, 0.8).unwrap();
+		light.set_powerup(Powerup::custom(green, 50.0)).await.unwrap();
+		let request: LightSetPowerupRequest =
+			serde_json::from_str(transport.requests().pop().unwrap().body.unwrap().as_str()).unwrap();
+		let xy = request.powerup.color.unwrap().color.unwrap().xy;
+		assert_eq!(xy, Gamut::C.restrain(&green));
+		assert_eq!(
+			light
+				.powerup
+				.as_ref()
+				.unwrap()
+				.color
+				.as_ref()
+				.unwrap()
+				.color
+				.as_ref()
+				.unwrap()
+				.xy,
+			xy
+		);
+	}
+
+	#[tokio::test]
+	async fn set_powerup_unsupported() {
+		let red = Component::new(0.6, 0.3).unwrap();
+
+		let (transport, mut light) = light_with(&[DIMMING, POWERUP]).await;
+		assert!(matches!(
+			light.set_powerup(Powerup::custom(red, 50.0)).await,
+			Err(HueError::Unsupported)
+		));
+		assert!(only_read(&transport));
+
+		let (transport, mut light) = light_with(&[POWERUP]).await;
+		assert!(matches!(
+			light.set_powerup(Powerup::custom(red, 50.0)).await,
+			Err(HueError::Unsupported)
+		));
+		assert!(only_read(&transport));
+
+		// without color nor dimming, only switching is left.
+		light.set_powerup(Powerup::off()).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn effect_unknown() {
+		let (transport, mut light) = light_with(&[
+			r#""effects": {"status": "no_effect", "status_values": ["no_effect", "future"], "effect_values": ["future"]}"#,
+			r#""timed_effects": {"status": "no_effect", "status_values": ["no_effect"], "effect_values": ["future"]}"#,
+			r#""alert": {"action_values": ["future"]}"#,
+			r#""signaling": {"signal_values": ["future"]}"#,
+		])
+		.await;
+
+		assert!(matches!(
+			light.set_effect(Effect::Unknown).await,
+			Err(HueError::Unsupported)
+		));
+		assert!(matches!(
+			light
+				.set_timed_effect(TimedEffect::Unknown, Duration::from_secs(60))
+				.await,
+			Err(HueError::Unsupported)
+		));
+		assert!(matches!(light.alert().await, Err(HueError::Unsupported)));
+		assert!(matches!(
+			light.signal(Signal::Unknown, Duration::from_secs(5), &[]).await,
+			Err(HueError::Unsupported)
+		));
+		assert!(only_read(&transport));
+	}
+
+	#[tokio::test]
+	async fn signal() {
+		let (transport, light) = light_with(&[
+			COLOR,
+			r#""signaling": {"signal_values": ["no_signal", "on_off", "on_off_color", "alternating"]}"#,
+		])
+		.await;
+
+		// beyond the green of gamut C.
+		let green = Component::new(0.1, 0.8).unwrap();
+		light
+			.signal(Signal::OnOffColor, Duration::from_secs(5), &[green])
+			.await
+			.unwrap();
+		let request: LightSignalRequest =
+			serde_json::from_str(transport.requests().pop().unwrap().body.unwrap().as_str()).unwrap();
+		assert_eq!(request.signaling.colors.len(), 1);
+		assert_eq!(request.signaling.colors[0].xy, Gamut::C.restrain(&green));
+
+		let red = Component::new(0.6, 0.3).unwrap();
+		light
+			.signal(Signal::Alternating, Duration::from_secs(5), &[red, green])
+			.await
+			.unwrap();
+		let request: LightSignalRequest =
+			serde_json::from_str(transport.requests().pop().unwrap().body.unwrap().as_str()).unwrap();
+		assert_eq!(request.signaling.colors.len(), 2);
+	}
+
+	#[tokio::test]
+	async fn signal_colors_mismatch() {
+		let signaling = r#""signaling": {"signal_values": ["on_off", "on_off_color", "alternating"]}"#;
+		let red = Component::new(0.6, 0.3).unwrap();
+		let duration = Duration::from_secs(5);
+
+		let (transport, light) = light_with(&[COLOR, signaling]).await;
+		for (signal, colors) in [
+			(Signal::OnOff, vec![red]),
+			(Signal::OnOffColor, vec![]),
+			(Signal::OnOffColor, vec![red, red]),
+			(Signal::Alternating, vec![red]),
+		] {
+			assert!(matches!(
+				light.signal(signal, duration, &colors).await,
+				Err(HueError::Unsupported)
+			));
+		}
+		assert!(only_read(&transport));
+
+		// without color, colors can't be shown at all.
+		let (transport, light) = light_with(&[signaling]).await;
+		assert!(matches!(
+			light.signal(Signal::OnOffColor, duration, &[red]).await,
+			Err(HueError::Unsupported)
+		));
+		assert!(only_read(&transport));
+	}
 }
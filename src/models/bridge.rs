@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::generic::{GenericIdentifier, Resource};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeZone {
+	pub time_zone: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBridgeResponseItem {
+	pub id: Uuid,
+	pub owner: Option<GenericIdentifier>,
+	pub bridge_id: String,
+	pub time_zone: TimeZone,
+
+	#[serde(rename = "type")]
+	pub r#type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBridgeHomeResponseItem {
+	pub id: Uuid,
+	pub children: HashSet<GenericIdentifier>,
+	pub services: HashSet<GenericIdentifier>,
+
+	#[serde(rename = "type")]
+	pub r#type: String,
+}
+
+impl Resource for GetBridgeResponseItem {
+	const TYPE: &'static str = "bridge";
+}
+
+impl Resource for GetBridgeHomeResponseItem {
+	const TYPE: &'static str = "bridge_home";
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_bridge_home_response_item_deserialize() {
+		let home: GetBridgeHomeResponseItem = serde_json::from_str(
+			r#"{
+				"id": "0b7a3d6e-8f3e-4b2c-9d3e-1f2a3b4c5d6e",
+				"children": [
+					{"rid": "1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f", "rtype": "room"},
+					{"rid": "2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a", "rtype": "device"}
+				],
+				"services": [{"rid": "3e4f5a6b-7c8d-4e9f-0a1b-2c3d4e5f6a7b", "rtype": "grouped_light"}],
+				"type": "bridge_home"
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(home.children.len(), 2);
+		assert_eq!(home.services.iter().next().unwrap().rtype, "grouped_light");
+	}
+}
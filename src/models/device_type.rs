@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use regex::Regex;
@@ -40,9 +41,9 @@ impl DeviceType {
 	}
 }
 
-impl ToString for DeviceType {
-	fn to_string(&self) -> String {
-		format!("{}#{}", self.application_name, self.device_name)
+impl Display for DeviceType {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}#{}", self.application_name, self.device_name)
 	}
 }
 
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::models::generic::{GenericIdentifier, Metadata, ProductData};
+use crate::models::generic::{ClipResponse, GenericIdentifier, Metadata, ProductData, Resource};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetDevicesResponseItem {
@@ -16,8 +16,32 @@ pub struct GetDevicesResponseItem {
 	pub device_type: String,
 }
 
+pub type GetDevicesResponse = ClipResponse<GetDevicesResponseItem>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifyAction {
+	Identify,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceIdentifyRequestAction {
+	pub action: IdentifyAction,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetDevicesResponse {
-	pub data: Option<Vec<GetDevicesResponseItem>>,
-	pub error: Option<super::Error>,
+pub struct DeviceIdentifyRequest {
+	pub identify: DeviceIdentifyRequestAction,
+}
+
+impl Resource for GetDevicesResponseItem {
+	const TYPE: &'static str = "device";
+}
+
+impl DeviceIdentifyRequest {
+	pub fn new(action: IdentifyAction) -> DeviceIdentifyRequest {
+		DeviceIdentifyRequest {
+			identify: DeviceIdentifyRequestAction { action },
+		}
+	}
 }
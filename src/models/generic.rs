@@ -1,5 +1,12 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+/// A CLIP v2 resource, accessible through `clip/v2/resource/{type}`.
+pub trait Resource: DeserializeOwned {
+	/// Resource type, as used in the URL and in [`GenericIdentifier::rtype`].
+	const TYPE: &'static str;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct GenericIdentifier {
 	pub rid: uuid::Uuid,
@@ -11,16 +18,91 @@ pub struct GenericError {
 	pub description: String,
 }
 
+/// Envelope of every CLIP v2 response. The request succeeded only if `errors` is empty.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GenericResponse {
-	pub errors: Option<Vec<GenericError>>,
-	pub data: Option<Vec<GenericIdentifier>>,
+pub struct ClipResponse<T> {
+	#[serde(default)]
+	pub errors: Vec<GenericError>,
+	#[serde(default = "Vec::new")]
+	pub data: Vec<T>,
+}
+
+/// Response to a write, holding the affected resources.
+pub type GenericResponse = ClipResponse<GenericIdentifier>;
+
+pub type GetResourceResponse<T> = ClipResponse<T>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Archetype {
+	BridgeV2,
+	ClassicBulb,
+	SultanBulb,
+	FloodBulb,
+	SpotBulb,
+	CandleBulb,
+	LusterBulb,
+	PendantRound,
+	PendantLong,
+	CeilingRound,
+	CeilingSquare,
+	FloorShade,
+	FloorLantern,
+	TableShade,
+	RecessedCeiling,
+	RecessedFloor,
+	SingleSpot,
+	DoubleSpot,
+	TableWash,
+	WallLantern,
+	WallShade,
+	FlexibleLamp,
+	GroundSpot,
+	WallSpot,
+	Plug,
+	HueGo,
+	HueLightstrip,
+	HueIris,
+	HueBloom,
+	Bollard,
+	WallWasher,
+	HuePlay,
+	VintageBulb,
+	VintageCandleBulb,
+	EllipseBulb,
+	TriangleBulb,
+	SmallGlobeBulb,
+	LargeGlobeBulb,
+	EdisonBulb,
+	ChristmasTree,
+	StringLight,
+	HueCentris,
+	HueLightstripTv,
+	HueLightstripPc,
+	HueTube,
+	HueSigne,
+	PendantSpot,
+	CeilingHorizontal,
+	CeilingTube,
+	#[serde(rename = "unknown_archetype", other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Function {
+	Functional,
+	Decorative,
+	Mixed,
+	#[serde(other)]
+	Unknown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
-	pub archetype: String,
+	pub archetype: Archetype,
 	pub name: String,
+	pub function: Option<Function>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,3 +114,21 @@ pub struct ProductData {
 	pub product_name: String,
 	pub software_version: String,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn metadata_deserialize() {
+		let metadata: Metadata =
+			serde_json::from_str(r#"{"name": "Desk", "archetype": "hue_lightstrip_pc", "function": "decorative"}"#)
+				.unwrap();
+		assert_eq!(metadata.archetype, Archetype::HueLightstripPc);
+		assert_eq!(metadata.function, Some(Function::Decorative));
+
+		let metadata: Metadata = serde_json::from_str(r#"{"name": "Hall", "archetype": "some_new_lamp"}"#).unwrap();
+		assert_eq!(metadata.archetype, Archetype::Unknown);
+		assert_eq!(metadata.function, None);
+	}
+}
@@ -1,6 +1,12 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
-use crate::color::{Color, Component, Temperature};
+use crate::color::{Color, Component, Gradient, GradientMode, GradientPoint, Temperature};
+use crate::effect::{Alert, AlertAction, Effect, Effects, Signal, Signaling, TimedEffect, TimedEffects};
+use crate::models::generic::{Archetype, GenericIdentifier, Metadata};
+use crate::models::generic::{ClipResponse, Resource};
+use crate::powerup::Powerup;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct On {
@@ -13,24 +19,42 @@ pub struct Dimming {
 	pub min_dim_level: f32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetLightsResponse {
-	pub data: Option<Vec<GetLightsResponseItem>>,
-	pub error: Option<crate::models::Error>,
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightMode {
+	Normal,
+	Streaming,
+	#[serde(other)]
+	Unknown,
 }
 
+pub type GetLightsResponse = ClipResponse<GetLightsResponseItem>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetLightsResponseItem {
 	#[serde(rename = "type")]
 	pub r#type: String,
 
 	pub id: uuid::Uuid,
-	pub metadata: super::generic::Metadata,
+	pub owner: GenericIdentifier,
+	pub metadata: Metadata,
+	pub mode: Option<LightMode>,
 	pub dimming: Option<Dimming>,
 	pub on: On,
 
 	pub color: Option<Color>,
 	pub color_temperature: Option<Temperature>,
+	pub gradient: Option<Gradient>,
+
+	pub effects: Option<Effects>,
+	pub timed_effects: Option<TimedEffects>,
+	pub alert: Option<Alert>,
+	pub signaling: Option<Signaling>,
+	pub powerup: Option<Powerup>,
+}
+
+impl Resource for GetLightsResponseItem {
+	const TYPE: &'static str = "light";
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +72,16 @@ pub struct LightSetColorRequest {
 	pub color: LightSetColorRequestXY,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetTemperatureRequestMirek {
+	pub mirek: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetTemperatureRequest {
+	pub color_temperature: LightSetTemperatureRequestMirek,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LightSetBrightnessRequestBrightness {
 	pub brightness: f32,
@@ -58,6 +92,81 @@ pub struct LightSetBrightnessRequest {
 	pub dimming: LightSetBrightnessRequestBrightness,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetPowerupRequest {
+	pub powerup: Powerup,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetMetadataRequestMetadata {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub archetype: Option<Archetype>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetMetadataRequest {
+	pub metadata: LightSetMetadataRequestMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetGradientRequestGradient {
+	pub points: Vec<GradientPoint>,
+	pub mode: GradientMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetGradientRequest {
+	pub gradient: LightSetGradientRequestGradient,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetEffectRequestEffect {
+	pub effect: Effect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetEffectRequest {
+	pub effects: LightSetEffectRequestEffect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetTimedEffectRequestEffect {
+	pub effect: TimedEffect,
+	pub duration: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSetTimedEffectRequest {
+	pub timed_effects: LightSetTimedEffectRequestEffect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightAlertRequestAction {
+	pub action: AlertAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightAlertRequest {
+	pub alert: LightAlertRequestAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSignalRequestSignal {
+	pub signal: Signal,
+	pub duration: u64,
+
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub colors: Vec<LightSetColorRequestXY>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightSignalRequest {
+	pub signaling: LightSignalRequestSignal,
+}
+
 impl LightOnRequest {
 	pub fn new(on: bool) -> LightOnRequest {
 		LightOnRequest { on: On { on } }
@@ -72,12 +181,125 @@ impl LightSetColorRequest {
 	}
 }
 
+impl LightSetTemperatureRequest {
+	pub fn new(mirek: u32) -> LightSetTemperatureRequest {
+		LightSetTemperatureRequest {
+			color_temperature: LightSetTemperatureRequestMirek { mirek },
+		}
+	}
+}
+
 impl LightSetBrightnessRequest {
 	pub fn new(brightness: f32) -> LightSetBrightnessRequest {
 		LightSetBrightnessRequest {
 			dimming: LightSetBrightnessRequestBrightness {
-				brightness: brightness.max(0.0).min(100.0),
+				brightness: brightness.clamp(0.0, 100.0),
+			},
+		}
+	}
+}
+
+impl LightSetPowerupRequest {
+	pub fn new(powerup: Powerup) -> LightSetPowerupRequest {
+		LightSetPowerupRequest { powerup }
+	}
+}
+
+impl LightSetMetadataRequest {
+	pub fn name(name: String) -> LightSetMetadataRequest {
+		LightSetMetadataRequest {
+			metadata: LightSetMetadataRequestMetadata {
+				name: Some(name),
+				archetype: None,
+			},
+		}
+	}
+
+	pub fn archetype(archetype: Archetype) -> LightSetMetadataRequest {
+		LightSetMetadataRequest {
+			metadata: LightSetMetadataRequestMetadata {
+				name: None,
+				archetype: Some(archetype),
+			},
+		}
+	}
+}
+
+impl LightSetGradientRequest {
+	pub fn new(points: Vec<Component>, mode: GradientMode) -> LightSetGradientRequest {
+		LightSetGradientRequest {
+			gradient: LightSetGradientRequestGradient {
+				points: points.into_iter().map(GradientPoint::new).collect(),
+				mode,
+			},
+		}
+	}
+}
+
+impl LightSetEffectRequest {
+	pub fn new(effect: Effect) -> LightSetEffectRequest {
+		LightSetEffectRequest {
+			effects: LightSetEffectRequestEffect { effect },
+		}
+	}
+}
+
+impl LightSetTimedEffectRequest {
+	pub fn new(effect: TimedEffect, duration: Duration) -> LightSetTimedEffectRequest {
+		LightSetTimedEffectRequest {
+			timed_effects: LightSetTimedEffectRequestEffect {
+				effect,
+				duration: duration.as_millis() as u64,
+			},
+		}
+	}
+}
+
+impl LightAlertRequest {
+	pub fn new(action: AlertAction) -> LightAlertRequest {
+		LightAlertRequest {
+			alert: LightAlertRequestAction { action },
+		}
+	}
+}
+
+impl LightSignalRequest {
+	pub fn new(signal: Signal, duration: Duration, colors: Vec<Component>) -> LightSignalRequest {
+		LightSignalRequest {
+			signaling: LightSignalRequestSignal {
+				signal,
+				duration: duration.as_millis() as u64,
+				colors: colors.into_iter().map(|xy| LightSetColorRequestXY { xy }).collect(),
 			},
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_lights_response_item_deserialize() {
+		let light: GetLightsResponseItem = serde_json::from_str(
+			r#"{
+				"id": "3f5c2a5e-0f4d-4a5e-8b0b-0b3b7d1c9a11",
+				"owner": {"rid": "6b8e0e9a-1c3d-4b6a-9a4e-2f1d3c5b7a90", "rtype": "device"},
+				"metadata": {"name": "Living room", "archetype": "sultan_bulb", "function": "mixed"},
+				"on": {"on": true},
+				"dimming": {"brightness": 42.0, "min_dim_level": 0.2},
+				"mode": "normal",
+				"signaling": {"signal_values": ["no_signal", "on_off"]},
+				"powerup": {"preset": "last_on_state", "configured": true, "on": {"mode": "previous"}},
+				"type": "light"
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(light.owner.rtype, "device");
+		assert_eq!(light.metadata.archetype, Archetype::SultanBulb);
+		assert_eq!(light.mode, Some(LightMode::Normal));
+		assert!(light.signaling.unwrap().supports(crate::effect::Signal::OnOff));
+		assert!(light.powerup.unwrap().configured);
+	}
+}
@@ -1,3 +1,4 @@
+pub mod bridge;
 pub mod config;
 pub mod create_user;
 pub mod device_type;
@@ -8,4 +9,4 @@ pub mod lights;
 
 pub use config::Config;
 pub use error::Error;
-pub use generic::GenericResponse;
+pub use generic::{ClipResponse, GenericResponse};
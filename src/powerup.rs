@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+use crate::color::Component;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerupPreset {
+	Safety,
+	Powerfail,
+	LastOnState,
+	Custom,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerupOnMode {
+	On,
+	Toggle,
+	Previous,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerupDimmingMode {
+	Dimming,
+	Previous,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerupColorMode {
+	ColorTemperature,
+	Color,
+	Previous,
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupOnState {
+	pub on: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupOn {
+	pub mode: PowerupOnMode,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub on: Option<PowerupOnState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupBrightness {
+	pub brightness: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupDimming {
+	pub mode: PowerupDimmingMode,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub dimming: Option<PowerupBrightness>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupMirek {
+	pub mirek: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupXY {
+	pub xy: Component,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerupColor {
+	pub mode: PowerupColorMode,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub color_temperature: Option<PowerupMirek>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub color: Option<PowerupXY>,
+}
+
+/// What a light does when it is powered on.
+///
+/// Apart from [`PowerupPreset::Custom`], the presets are handled by the bridge itself, so only the preset is sent when
+/// changing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Powerup {
+	pub preset: PowerupPreset,
+
+	#[serde(default, skip_serializing)]
+	pub configured: bool,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub on: Option<PowerupOn>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub dimming: Option<PowerupDimming>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub color: Option<PowerupColor>,
+}
+
+impl Powerup {
+	fn preset(preset: PowerupPreset) -> Powerup {
+		Powerup {
+			preset,
+			configured: false,
+			on: None,
+			dimming: None,
+			color: None,
+		}
+	}
+
+	/// Light turns on at full brightness in warm white.
+	pub fn safety() -> Powerup {
+		Self::preset(PowerupPreset::Safety)
+	}
+
+	/// Light goes back to its previous state after a power failure, and to the safety state otherwise.
+	pub fn powerfail() -> Powerup {
+		Self::preset(PowerupPreset::Powerfail)
+	}
+
+	/// Light turns on with its last on state.
+	pub fn last_on_state() -> Powerup {
+		Self::preset(PowerupPreset::LastOnState)
+	}
+
+	/// Light turns on with its previous on/off state.
+	pub fn previous() -> Powerup {
+		Powerup {
+			on: Some(PowerupOn {
+				mode: PowerupOnMode::Previous,
+				on: None,
+			}),
+			..Self::preset(PowerupPreset::Custom)
+		}
+	}
+
+	/// Light stays off when powered on.
+	pub fn off() -> Powerup {
+		Powerup {
+			on: Some(PowerupOn {
+				mode: PowerupOnMode::On,
+				on: Some(PowerupOnState { on: false }),
+			}),
+			..Self::preset(PowerupPreset::Custom)
+		}
+	}
+
+	/// Light turns on with the given color and brightness (in percent).
+	pub fn custom(xy: Component, brightness: f32) -> Powerup {
+		Powerup {
+			on: Some(PowerupOn {
+				mode: PowerupOnMode::On,
+				on: Some(PowerupOnState { on: true }),
+			}),
+			dimming: Some(PowerupDimming {
+				mode: PowerupDimmingMode::Dimming,
+				dimming: Some(PowerupBrightness {
+					brightness: brightness.clamp(0.0, 100.0),
+				}),
+			}),
+			color: Some(PowerupColor {
+				mode: PowerupColorMode::Color,
+				color_temperature: None,
+				color: Some(PowerupXY { xy }),
+			}),
+			..Self::preset(PowerupPreset::Custom)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn powerup_deserialize() {
+		let powerup: Powerup = serde_json::from_str(
+			r#"{
+				"preset": "safety",
+				"configured": true,
+				"on": {"mode": "on", "on": {"on": true}},
+				"dimming": {"mode": "dimming", "dimming": {"brightness": 100.0}},
+				"color": {"mode": "color_temperature", "color_temperature": {"mirek": 366}}
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(powerup.preset, PowerupPreset::Safety);
+		assert!(powerup.configured);
+		assert_eq!(powerup.color.unwrap().color_temperature.unwrap().mirek, 366);
+	}
+
+	#[test]
+	fn powerup_serialize() {
+		assert_eq!(
+			serde_json::to_string(&Powerup::last_on_state()).unwrap(),
+			r#"{"preset":"last_on_state"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&Powerup::off()).unwrap(),
+			r#"{"preset":"custom","on":{"mode":"on","on":{"on":false}}}"#
+		);
+	}
+}
@@ -0,0 +1,269 @@
+//! Recording of bridge traffic into fixture files, and their replay, so tests can run offline with real payloads.
+//!
+//! ```no_run
+//! # use std::net::Ipv4Addr;
+//! # use std::sync::Arc;
+//! # use huehue::{Hue, HueError};
+//! # use huehue::models::device_type::DeviceType;
+//! # use huehue::recording::ReplayTransport;
+//! #
+//! # async fn record(mut hue: Hue) -> Result<(), HueError> {
+//! let recording = hue.record().await?;
+//! hue.lights().await?;
+//! recording
+//! 	.save("lights.json")
+//! 	.expect("Failed to save fixture.");
+//! # Ok(())
+//! # }
+//! #
+//! # async fn replay(device_type: DeviceType) -> Result<(), HueError> {
+//! let replay = ReplayTransport::load("lights.json").expect("Failed to load fixture.");
+//! let hue = Hue::new_with_transport(
+//! 	Ipv4Addr::LOCALHOST,
+//! 	device_type,
+//! 	Some("key".to_owned()),
+//! 	Arc::new(replay),
+//! )
+//! .await?;
+//! let lights = hue.lights().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::transport::{Method, Request, Response, Transport, TransportFuture};
+
+/// Replaces application keys in fixtures.
+pub const REDACTED: &str = "REDACTED";
+
+/// A request and the response it got.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+	pub method: Method,
+	/// URL path, including the query.
+	pub path: String,
+	pub application_key: Option<String>,
+	pub request: Option<String>,
+	pub status: u16,
+	pub response: String,
+}
+
+/// Transport recording every exchange of another transport, with application keys redacted.
+#[derive(Debug)]
+pub struct RecordingTransport {
+	inner: Arc<dyn Transport>,
+	keys: Mutex<Vec<String>>,
+	exchanges: Mutex<Vec<Exchange>>,
+}
+
+/// Transport serving exchanges previously recorded by a [`RecordingTransport`].
+///
+/// Responses to the same method and path are served in the order they were recorded, the last one being repeated once
+/// they are exhausted. Requests never recorded get an empty `404 Not Found`.
+#[derive(Debug)]
+pub struct ReplayTransport {
+	responses: Mutex<HashMap<(Method, String), VecDeque<Response>>>,
+}
+
+fn path(request: &Request) -> String {
+	match request.url.query() {
+		Some(query) => format!("{}?{}", request.url.path(), query),
+		None => request.url.path().to_owned(),
+	}
+}
+
+/// Keys created by the bridge in a response to `POST /api`, which are only known once the bridge has answered.
+fn created_keys(body: &str) -> Vec<String> {
+	let items: Vec<serde_json::Value> = serde_json::from_str(body).unwrap_or_default();
+	items
+		.iter()
+		.filter_map(|item| item.get("success"))
+		.flat_map(|success| {
+			["username", "clientkey"]
+				.into_iter()
+				.filter_map(|field| success.get(field)?.as_str().map(str::to_owned))
+		})
+		.collect()
+}
+
+impl RecordingTransport {
+	pub fn new(inner: Arc<dyn Transport>) -> RecordingTransport {
+		RecordingTransport {
+			inner,
+			keys: Mutex::new(Vec::new()),
+			exchanges: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// Redacts `key` from everything recorded from now on. Application keys sent along requests, and the ones created
+	/// through [`Hue::authorize`](crate::Hue::authorize), are redacted without being added.
+	pub fn redact_key(&self, key: &str) {
+		let mut keys = self.keys.lock().unwrap();
+		if !key.is_empty() && !keys.iter().any(|known| known == key) {
+			keys.push(key.to_owned());
+		}
+	}
+
+	/// Every exchange recorded so far, in order.
+	pub fn exchanges(&self) -> Vec<Exchange> {
+		self.exchanges.lock().unwrap().clone()
+	}
+
+	/// Writes every exchange recorded so far into a fixture file at `path`.
+	pub fn save<P>(&self, path: P) -> io::Result<()>
+	where
+		P: AsRef<Path>,
+	{
+		let contents = serde_json::to_string_pretty(&self.exchanges())?;
+		fs::write(path, contents)
+	}
+
+	fn redact(&self, text: &str) -> String {
+		self.keys
+			.lock()
+			.unwrap()
+			.iter()
+			.fold(text.to_owned(), |text, key| text.replace(key.as_str(), REDACTED))
+	}
+}
+
+impl Transport for RecordingTransport {
+	fn send(&self, request: Request) -> TransportFuture<'_> {
+		Box::pin(async move {
+			if let Some(key) = &request.application_key {
+				self.redact_key(key.as_str());
+			}
+
+			let method = request.method;
+			let path = path(&request);
+			let application_key = request.application_key.as_ref().map(|_| REDACTED.to_owned());
+			let body = request.body.clone();
+
+			let response = self.inner.send(request).await?;
+			if method == Method::Post && path == "/api" {
+				for key in created_keys(response.body.as_str()) {
+					self.redact_key(key.as_str());
+				}
+			}
+
+			self.exchanges.lock().unwrap().push(Exchange {
+				method,
+				path: self.redact(path.as_str()),
+				application_key,
+				request: body.map(|body| self.redact(body.as_str())),
+				status: response.status,
+				response: self.redact(response.body.as_str()),
+			});
+
+			Ok(response)
+		})
+	}
+}
+
+impl ReplayTransport {
+	pub fn new(exchanges: Vec<Exchange>) -> ReplayTransport {
+		let mut responses: HashMap<(Method, String), VecDeque<Response>> = HashMap::new();
+		for exchange in exchanges {
+			responses
+				.entry((exchange.method, exchange.path))
+				.or_default()
+				.push_back(Response {
+					status: exchange.status,
+					body: exchange.response,
+				});
+		}
+
+		ReplayTransport {
+			responses: Mutex::new(responses),
+		}
+	}
+
+	/// Reads a fixture file written by [`RecordingTransport::save`].
+	pub fn load<P>(path: P) -> io::Result<ReplayTransport>
+	where
+		P: AsRef<Path>,
+	{
+		let exchanges: Vec<Exchange> = serde_json::from_str(fs::read_to_string(path)?.as_str())?;
+		Ok(Self::new(exchanges))
+	}
+}
+
+impl Transport for ReplayTransport {
+	fn send(&self, request: Request) -> TransportFuture<'_> {
+		let mut responses = self.responses.lock().unwrap();
+		let response = match responses.get_mut(&(request.method, path(&request))) {
+			Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+			Some(queue) if !queue.is_empty() => queue.front().unwrap().clone(),
+			_ => {
+				Response {
+					status: 404,
+					body: String::new(),
+				}
+			},
+		};
+
+		Box::pin(async move { Ok(response) })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use url::Url;
+
+	use super::*;
+	use crate::transport::MemoryTransport;
+
+	#[tokio::test]
+	async fn record_and_replay() {
+		let memory = Arc::new(MemoryTransport::new());
+		memory.respond(
+			Method::Post,
+			"/api",
+			200,
+			r#"[{"success": {"username": "secret-key"}}]"#,
+		);
+		memory.respond(
+			Method::Get,
+			"/clip/v2/resource/light",
+			200,
+			r#"{"errors": [], "data": []}"#,
+		);
+
+		let recording = RecordingTransport::new(memory);
+		let url = |path: &str| Url::parse(format!("https://127.0.0.1{}", path).as_str()).unwrap();
+		recording
+			.get(url("/clip/v2/resource/light"), Some("secret-key".to_owned()))
+			.await
+			.unwrap();
+		recording
+			.post(url("/api"), None, r#"{"devicetype": "huehue#test"}"#.to_owned())
+			.await
+			.unwrap();
+
+		let exchanges = recording.exchanges();
+		assert_eq!(exchanges.len(), 2);
+		assert_eq!(exchanges[0].application_key.as_deref(), Some(REDACTED));
+		assert_eq!(exchanges[1].response, r#"[{"success": {"username": "REDACTED"}}]"#);
+
+		let serialized = serde_json::to_string(&exchanges).unwrap();
+		assert!(!serialized.contains("secret-key"));
+
+		let replay = ReplayTransport::new(serde_json::from_str(serialized.as_str()).unwrap());
+		for _ in 0..2 {
+			let response = replay.get(url("/clip/v2/resource/light"), None).await.unwrap();
+			assert_eq!(response.status, 200);
+			assert_eq!(response.body, r#"{"errors": [], "data": []}"#);
+		}
+		assert_eq!(
+			replay.get(url("/clip/v2/resource/device"), None).await.unwrap().status,
+			404
+		);
+	}
+}
@@ -0,0 +1,182 @@
+//! HTTP transport used to talk to the bridge.
+//!
+//! [`Hue`](crate::Hue) uses [`ReqwestTransport`] by default, whose TLS backend is picked through the `native-tls`
+//! (default) and `rustls` features. Any other [`Transport`] can be given to
+//! [`Hue::new_with_transport`](crate::Hue::new_with_transport), like the [`MemoryTransport`], which serves canned
+//! responses for tests, or the [`ReplayTransport`](crate::recording::ReplayTransport), which serves traffic
+//! previously recorded from a real bridge.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::http::{self, HueError};
+
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, HueError>> + Send + 'a>>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+	Get,
+	Put,
+	Post,
+	Delete,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Request {
+	pub method: Method,
+	pub url: Url,
+	pub application_key: Option<String>,
+	/// JSON payload.
+	pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Response {
+	pub status: u16,
+	pub body: String,
+}
+
+/// Sends requests to the bridge.
+///
+/// Only [`Transport::send`] must be implemented; the remaining methods are shorthands for it.
+pub trait Transport: Debug + Send + Sync {
+	fn send(&self, request: Request) -> TransportFuture<'_>;
+
+	fn get(&self, url: Url, application_key: Option<String>) -> TransportFuture<'_> {
+		self.send(Request {
+			method: Method::Get,
+			url,
+			application_key,
+			body: None,
+		})
+	}
+
+	fn put(&self, url: Url, application_key: Option<String>, body: String) -> TransportFuture<'_> {
+		self.send(Request {
+			method: Method::Put,
+			url,
+			application_key,
+			body: Some(body),
+		})
+	}
+
+	fn post(&self, url: Url, application_key: Option<String>, body: String) -> TransportFuture<'_> {
+		self.send(Request {
+			method: Method::Post,
+			url,
+			application_key,
+			body: Some(body),
+		})
+	}
+
+	fn delete(&self, url: Url, application_key: Option<String>) -> TransportFuture<'_> {
+		self.send(Request {
+			method: Method::Delete,
+			url,
+			application_key,
+			body: None,
+		})
+	}
+}
+
+/// Transport backed by [`reqwest`], validating the bridge certificate.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+	client: reqwest::Client,
+}
+
+/// Transport serving canned responses, and recording every request it receives. Meant for tests.
+///
+/// Requests without a matching response get an empty `404 Not Found`.
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+	responses: Mutex<HashMap<(Method, String), Response>>,
+	requests: Mutex<Vec<Request>>,
+}
+
+impl ReqwestTransport {
+	pub fn new() -> ReqwestTransport {
+		ReqwestTransport { client: http::build() }
+	}
+}
+
+impl Default for ReqwestTransport {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Transport for ReqwestTransport {
+	fn send(&self, request: Request) -> TransportFuture<'_> {
+		Box::pin(async move {
+			let method = match request.method {
+				Method::Get => reqwest::Method::GET,
+				Method::Put => reqwest::Method::PUT,
+				Method::Post => reqwest::Method::POST,
+				Method::Delete => reqwest::Method::DELETE,
+			};
+
+			let mut builder = self.client.request(method, request.url);
+			if let Some(application_key) = request.application_key {
+				builder = builder.header(http::HUE_APPLICATION_KEY_HEADER, application_key);
+			}
+			if let Some(body) = request.body {
+				builder = builder.header(CONTENT_TYPE, "application/json").body(body);
+			}
+
+			let response = builder.send().await?;
+			let status = response.status().as_u16();
+			let body = response.text().await?;
+
+			Ok(Response { status, body })
+		})
+	}
+}
+
+impl MemoryTransport {
+	pub fn new() -> MemoryTransport {
+		Self::default()
+	}
+
+	/// Answers requests with `method` to `path` (like `/clip/v2/resource/light`) with `status` and `body`.
+	pub fn respond(&self, method: Method, path: &str, status: u16, body: &str) {
+		self.responses.lock().unwrap().insert(
+			(method, path.to_owned()),
+			Response {
+				status,
+				body: body.to_owned(),
+			},
+		);
+	}
+
+	/// Every request received so far, in order.
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+}
+
+impl Transport for MemoryTransport {
+	fn send(&self, request: Request) -> TransportFuture<'_> {
+		let response = self
+			.responses
+			.lock()
+			.unwrap()
+			.get(&(request.method, request.url.path().to_owned()))
+			.cloned()
+			.unwrap_or(Response {
+				status: 404,
+				body: String::new(),
+			});
+		self.requests.lock().unwrap().push(request);
+
+		Box::pin(async move { Ok(response) })
+	}
+}